@@ -0,0 +1,38 @@
+#import bevy_pbr::{mesh_functions, view_transformations::position_world_to_clip}
+
+struct OutlineMaterial {
+    color: vec4<f32>,
+    thickness: f32,
+}
+
+@group(2) @binding(0) var<uniform> material: OutlineMaterial;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+};
+
+// Inverted hull: push the back faces out along the normal so they peek out around the mesh.
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    let world_position = mesh_functions::mesh_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0),
+    );
+    let world_normal = mesh_functions::mesh_normal_local_to_world(vertex.normal, vertex.instance_index);
+
+    var out: VertexOutput;
+    out.clip_position = position_world_to_clip(world_position.xyz + world_normal * material.thickness);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return material.color;
+}
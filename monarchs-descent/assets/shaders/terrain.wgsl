@@ -0,0 +1,50 @@
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::alpha_discard,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_io::{VertexOutput, FragmentOutput},
+    pbr_deferred_functions::deferred_output,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{VertexOutput, FragmentOutput},
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing},
+}
+#endif
+
+struct SplatMaterial {
+    sand: vec4<f32>,
+    grass: vec4<f32>,
+    rock: vec4<f32>,
+    snow: vec4<f32>,
+}
+
+@group(2) @binding(100) var<uniform> splat: SplatMaterial;
+@group(2) @binding(101) var splat_map: texture_2d<f32>;
+@group(2) @binding(102) var splat_sampler: sampler;
+
+@fragment
+fn fragment(in: VertexOutput, @builtin(front_facing) is_front: bool) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    // The mesh UVs run from the first texel's centre to the last one's.
+    let texels = vec2<f32>(textureDimensions(splat_map));
+    let uv = (in.uv * (texels - 1.0) + 0.5) / texels;
+    let weights = textureSample(splat_map, splat_sampler, uv);
+    let layers = splat.sand * weights.r + splat.grass * weights.g + splat.rock * weights.b + splat.snow * weights.a;
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color * layers);
+
+#ifdef PREPASS_PIPELINE
+    let out = deferred_output(in, pbr_input);
+#else
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#endif
+
+    return out;
+}
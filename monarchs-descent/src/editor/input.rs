@@ -1,10 +1,19 @@
 use crate::editor::EditorState;
+use crate::gameplay::settings::bindings::{
+    BindInputs, BindingContext, InputBinding, InputBindings, RebindAppExt,
+};
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_input_context::<EditorActions>();
 
+    app.add_rebindable::<ToggleEditor>(
+        BindingContext::Editor,
+        "Toggle editor",
+        InputBinding::keyboard(KeyCode::Backslash),
+    );
+
     app.add_observer(bind_editor_actions);
 
     app.add_observer(toggle_editor);
@@ -13,12 +22,13 @@ pub(super) fn plugin(app: &mut App) {
 fn bind_editor_actions(
     trigger: Trigger<Binding<EditorActions>>,
     mut editor_actions: Query<&mut Actions<EditorActions>>,
+    bindings: InputBindings,
 ) {
     let mut actions = editor_actions.get_mut(trigger.target()).unwrap();
 
     actions
         .bind::<ToggleEditor>()
-        .to(KeyCode::Backslash)
+        .to_binding(bindings.get::<ToggleEditor>())
         .with_conditions(Release::default());
 }
 
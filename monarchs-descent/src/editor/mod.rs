@@ -3,6 +3,8 @@ mod toggle_ui;
 
 use crate::editor::input::EditorActions;
 use crate::editor::toggle_ui::toggle;
+use crate::gameplay::day_night::TimeOfDay;
+use crate::gameplay::streaming::{Cell, StreamingDebug};
 use avian3d::prelude::{Physics, PhysicsGizmos, PhysicsTime};
 use bevy::asset::{ReflectAsset, UntypedAssetId};
 use bevy::prelude::*;
@@ -119,6 +121,8 @@ enum EditorWindowTabs {
     Assets,
     Inspector,
     Physics,
+    Streaming,
+    TimeOfDay,
 }
 
 #[derive(Eq, PartialEq)]
@@ -143,8 +147,15 @@ impl EditorUiState {
 
         let [game, inspector] =
             tree.split_right(NodeIndex::root(), 0.75, vec![EditorWindowTabs::Inspector]);
-        let [_physics, _inspector] =
-            tree.split_above(inspector, 0.2, vec![EditorWindowTabs::Physics]);
+        let [_physics, _inspector] = tree.split_above(
+            inspector,
+            0.2,
+            vec![
+                EditorWindowTabs::Physics,
+                EditorWindowTabs::Streaming,
+                EditorWindowTabs::TimeOfDay,
+            ],
+        );
         let [game, hierarchy] = tree.split_left(
             game,
             0.2,
@@ -251,6 +262,8 @@ impl egui_dock::TabViewer for EditorTabViewer<'_> {
                 );
             }
             EditorWindowTabs::Physics => physics_ui(ui, &mut self.world),
+            EditorWindowTabs::Streaming => streaming_ui(ui, self.world),
+            EditorWindowTabs::TimeOfDay => time_of_day_ui(ui, self.world),
         }
     }
 
@@ -331,6 +344,71 @@ fn physics_ui(ui: &mut Ui, world: &mut World) {
         });
 }
 
+fn streaming_ui(ui: &mut Ui, world: &mut World) {
+    ui.heading("World Streaming");
+
+    let (mut loaded, mut loading) = (0, 0);
+    for cell in world.query::<&Cell>().iter(world) {
+        match cell.loaded {
+            true => loaded += 1,
+            false => loading += 1,
+        }
+    }
+
+    egui::Grid::new("streaming grid")
+        .num_columns(2)
+        .spacing([40.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            let mut debug = world.resource_mut::<StreamingDebug>();
+
+            ui.label("Show cells");
+            let mut show_cells = debug.show_cells;
+            ui.add(toggle(&mut show_cells));
+            if show_cells != debug.show_cells {
+                debug.show_cells = show_cells;
+            }
+            ui.end_row();
+
+            ui.label("Loaded cells");
+            ui.label(loaded.to_string());
+            ui.end_row();
+
+            ui.label("Loading cells");
+            ui.label(loading.to_string());
+            ui.end_row();
+        });
+}
+
+fn time_of_day_ui(ui: &mut Ui, world: &mut World) {
+    ui.heading("Time of Day");
+
+    egui::Grid::new("time of day grid")
+        .num_columns(2)
+        .spacing([40.0, 4.0])
+        .striped(true)
+        .show(ui, |ui| {
+            let mut time_of_day = world.resource_mut::<TimeOfDay>();
+
+            ui.label("Hour");
+            ui.add(egui::Slider::new(&mut time_of_day.hour, 0.0..=24.0));
+            ui.end_row();
+
+            ui.label("Frozen");
+            ui.add(toggle(&mut time_of_day.frozen));
+            ui.end_row();
+
+            ui.label("Day length");
+            ui.add(
+                egui::DragValue::new(&mut time_of_day.day_length)
+                    .speed(1.0)
+                    .range(1.0..=f32::MAX)
+                    .suffix(" s"),
+            );
+            ui.end_row();
+        });
+}
+
 fn select_asset(
     ui: &mut Ui,
     type_registry: &TypeRegistry,
@@ -0,0 +1,129 @@
+use crate::AppState;
+use bevy::pbr::light_consts::lux;
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// How far the sun's path leans away from overhead, so it never lights the map straight down.
+const SUN_TILT: f32 = 0.35;
+const NOON_ILLUMINANCE: f32 = lux::AMBIENT_DAYLIGHT;
+const SUNRISE_KELVIN: f32 = 2000.0;
+const NOON_KELVIN: f32 = 5800.0;
+const DAY_AMBIENT: f32 = 80.0;
+const NIGHT_AMBIENT: f32 = 10.0;
+const DAY_AMBIENT_COLOR: Color = Color::srgb(0.85, 0.9, 1.0);
+const NIGHT_AMBIENT_COLOR: Color = Color::srgb(0.35, 0.4, 0.7);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TimeOfDay>().register_type::<Sun>();
+
+    app.init_resource::<TimeOfDay>();
+
+    app.add_systems(
+        Update,
+        (advance_time_of_day, update_sun, update_ambient_light)
+            .chain()
+            .run_if(in_state(AppState::Gameplay)),
+    )
+    .add_systems(OnExit(AppState::Gameplay), reset_ambient_light);
+}
+
+/// Clock of the in-game day, it runs on virtual time so it stops while the game is paused.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct TimeOfDay {
+    /// From 0 to 24, the sun rises at 6 and sets at 18.
+    pub hour: f32,
+    /// Seconds of unpaused game time a whole day takes.
+    pub day_length: f32,
+    /// Stops the clock on the current hour.
+    pub frozen: bool,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hour: 10.0,
+            day_length: 1200.0,
+            frozen: false,
+        }
+    }
+}
+
+impl TimeOfDay {
+    /// Direction towards the sun, rising along +X and passing a little towards +Z at noon.
+    pub fn sun_direction(&self) -> Vec3 {
+        let angle = (self.hour - 6.0) / 24.0 * TAU;
+        Quat::from_rotation_x(SUN_TILT) * Vec3::new(angle.cos(), angle.sin(), 0.0)
+    }
+
+    /// How much of the day's light there is, from 0 at night to 1 once the sun is up. Dusk
+    /// lingers a little after the sun has set.
+    pub fn daylight(&self) -> f32 {
+        ((self.sun_direction().y + 0.05) / 0.25).clamp(0.0, 1.0)
+    }
+}
+
+/// Directional light moved and coloured by the [`TimeOfDay`].
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct Sun;
+
+fn advance_time_of_day(time: Res<Time>, mut time_of_day: ResMut<TimeOfDay>) {
+    if time_of_day.frozen || time_of_day.day_length <= 0.0 {
+        return;
+    }
+
+    let hours = time.delta_secs() / time_of_day.day_length * 24.0;
+    time_of_day.hour = (time_of_day.hour + hours).rem_euclid(24.0);
+}
+
+fn update_sun(
+    time_of_day: Res<TimeOfDay>,
+    mut suns: Query<(&mut Transform, &mut DirectionalLight), With<Sun>>,
+) {
+    let direction = time_of_day.sun_direction();
+    // Low sun shines through more air and turns orange.
+    let height = direction.y.clamp(0.0, 1.0).sqrt();
+
+    for (mut transform, mut light) in suns.iter_mut() {
+        transform.look_to(-direction, Vec3::Y);
+        light.illuminance = NOON_ILLUMINANCE * time_of_day.daylight();
+        light.color = color_temperature(SUNRISE_KELVIN.lerp(NOON_KELVIN, height));
+    }
+}
+
+fn update_ambient_light(time_of_day: Res<TimeOfDay>, mut ambient: ResMut<AmbientLight>) {
+    let daylight = time_of_day.daylight();
+    ambient.brightness = NIGHT_AMBIENT.lerp(DAY_AMBIENT, daylight);
+    ambient.color = NIGHT_AMBIENT_COLOR.mix(&DAY_AMBIENT_COLOR, daylight);
+}
+
+/// The menus are lit like before gameplay started rather than by whatever hour was left.
+fn reset_ambient_light(mut ambient: ResMut<AmbientLight>) {
+    *ambient = AmbientLight::default();
+}
+
+/// Colour of a black body glowing at `kelvin`, Tanner Helland's fit of the blackbody curve.
+fn color_temperature(kelvin: f32) -> Color {
+    let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+
+    let red = match t <= 66.0 {
+        true => 255.0,
+        false => 329.69873 * (t - 60.0).powf(-0.13320476),
+    };
+    let green = match t <= 66.0 {
+        true => 99.4708 * t.ln() - 161.11957,
+        false => 288.12216 * (t - 60.0).powf(-0.07551485),
+    };
+    let blue = match t {
+        t if t >= 66.0 => 255.0,
+        t if t <= 19.0 => 0.0,
+        t => 138.51773 * (t - 10.0).ln() - 305.0448,
+    };
+
+    Color::srgb(
+        (red / 255.0).clamp(0.0, 1.0),
+        (green / 255.0).clamp(0.0, 1.0),
+        (blue / 255.0).clamp(0.0, 1.0),
+    )
+}
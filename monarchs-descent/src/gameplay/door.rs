@@ -0,0 +1,260 @@
+use crate::AppState;
+use crate::gameplay::interactable::{Interactable, Interacted};
+use crate::gameplay::items::inventory::Inventory;
+use crate::gameplay::player::Player;
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DoorState>()
+        .register_type::<SlidingDoor>()
+        .register_type::<HingedDoor>()
+        .register_type::<DoorLock>()
+        .register_type::<DoorKey>()
+        .register_type::<DoorTrigger>();
+
+    app.add_observer(interact_with_door).add_observer(open_door);
+
+    app.add_systems(Update, open_doors_from_triggers)
+        .add_systems(FixedUpdate, move_sliding_doors);
+}
+
+#[derive(Default, Component, Reflect, Debug, Copy, Clone, PartialEq)]
+#[reflect(Component)]
+pub enum DoorState {
+    #[default]
+    Closed,
+    Open,
+}
+
+impl DoorState {
+    fn toggled(self) -> Self {
+        match self {
+            DoorState::Closed => DoorState::Open,
+            DoorState::Open => DoorState::Closed,
+        }
+    }
+
+    /// What interacting with a door in this state will do.
+    fn verb(self) -> &'static str {
+        match self {
+            DoorState::Closed => "open",
+            DoorState::Open => "close",
+        }
+    }
+}
+
+/// Door that slides along `open_offset` when opened, driven kinematically.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(RigidBody::Kinematic, Interactable::new("open"), DoorState)]
+pub struct SlidingDoor {
+    pub open_offset: Vec3,
+    pub speed: f32,
+    closed_position: Option<Vec3>,
+}
+
+impl SlidingDoor {
+    pub fn new(open_offset: Vec3, speed: f32) -> Self {
+        Self {
+            open_offset,
+            speed,
+            closed_position: None,
+        }
+    }
+}
+
+/// Door that swings on a revolute joint and is pushed open by physics, see [`spawn_hinged_door`].
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(RigidBody::Dynamic)]
+pub struct HingedDoor;
+
+/// Keeps a door shut until opened by an actor carrying the matching [`DoorKey`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct DoorLock {
+    pub key: String,
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct DoorKey(pub String);
+
+/// Sensor volume that opens `door` while the player is inside it.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(Sensor, CollisionEventsEnabled)]
+pub struct DoorTrigger {
+    pub door: Entity,
+}
+
+/// Spawns a door leaf hinged on its left edge at `hinge`, returning the door entity.
+pub fn spawn_hinged_door(
+    commands: &mut Commands,
+    hinge: Transform,
+    size: Vec3,
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+) -> Entity {
+    let half_width = Vec3::X * size.x / 2.0;
+
+    let frame = commands
+        .spawn((
+            Name::new("Door Frame"),
+            StateScoped(AppState::Gameplay),
+            hinge,
+            RigidBody::Static,
+        ))
+        .id();
+
+    let door = commands
+        .spawn((
+            Name::new("Door Hinged"),
+            StateScoped(AppState::Gameplay),
+            HingedDoor,
+            Transform::from_translation(hinge.translation + hinge.rotation * half_width)
+                .with_rotation(hinge.rotation),
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Collider::cuboid(size.x, size.y, size.z),
+            AngularDamping(2.0),
+        ))
+        .id();
+
+    commands.spawn((
+        Name::new("Door Hinge"),
+        StateScoped(AppState::Gameplay),
+        RevoluteJoint::new(frame, door)
+            .with_local_anchor_2(-half_width)
+            .with_aligned_axis(Vec3::Y)
+            .with_angle_limits(-FRAC_PI_2, FRAC_PI_2),
+    ));
+
+    door
+}
+
+/// Triggered on a sliding door to open it from a script, unlocking it if needed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct OpenDoor;
+
+fn has_key(
+    actor: Entity,
+    lock: &DoorLock,
+    inventories: &Query<&Inventory>,
+    keys: &Query<&DoorKey>,
+) -> bool {
+    inventories.get(actor).is_ok_and(|inventory| {
+        inventory
+            .get_items()
+            .iter()
+            .flatten()
+            .any(|&item| keys.get(item).is_ok_and(|key| key.0 == lock.key))
+    })
+}
+
+fn interact_with_door(
+    trigger: Trigger<Interacted>,
+    mut commands: Commands,
+    mut doors: Query<(&mut DoorState, &mut Interactable, Option<&DoorLock>), With<SlidingDoor>>,
+    inventories: Query<&Inventory>,
+    keys: Query<&DoorKey>,
+) {
+    let Interacted { actor, target } = *trigger.event();
+
+    let Ok((mut state, mut interactable, lock)) = doors.get_mut(target) else {
+        return;
+    };
+
+    if let Some(lock) = lock {
+        if !has_key(actor, lock, &inventories, &keys) {
+            info!(?target, key = %lock.key, "door is locked");
+            return;
+        }
+
+        debug!(?target, key = %lock.key, "unlocking door");
+        commands.entity(target).remove::<DoorLock>();
+    }
+
+    *state = state.toggled();
+    interactable.verb = state.verb().to_string();
+}
+
+fn open_door(
+    trigger: Trigger<OpenDoor>,
+    mut commands: Commands,
+    mut doors: Query<(&mut DoorState, &mut Interactable), With<SlidingDoor>>,
+) {
+    let door = trigger.target();
+    let Ok((mut state, mut interactable)) = doors.get_mut(door) else {
+        warn!(?door, "only sliding doors can be opened by scripts");
+        return;
+    };
+
+    commands.entity(door).try_remove::<DoorLock>();
+    *state = DoorState::Open;
+    interactable.verb = state.verb().to_string();
+}
+
+fn open_doors_from_triggers(
+    mut started: EventReader<CollisionStarted>,
+    mut ended: EventReader<CollisionEnded>,
+    triggers: Query<&DoorTrigger>,
+    players: Query<(), With<Player>>,
+    colliders: Query<&ColliderOf>,
+    mut doors: Query<(&mut DoorState, &mut Interactable), (With<SlidingDoor>, Without<DoorLock>)>,
+) {
+    let is_player = |entity: Entity| {
+        let body = colliders
+            .get(entity)
+            .map_or(entity, |collider_of| collider_of.body);
+        players.contains(body)
+    };
+
+    let started = started
+        .read()
+        .map(|CollisionStarted(a, b)| (*a, *b, DoorState::Open));
+    let ended = ended
+        .read()
+        .map(|CollisionEnded(a, b)| (*a, *b, DoorState::Closed));
+
+    for (a, b, next) in started.chain(ended) {
+        let (trigger, other) = match triggers.contains(a) {
+            true => (a, b),
+            false => (b, a),
+        };
+
+        let Ok(door_trigger) = triggers.get(trigger) else {
+            continue;
+        };
+        if !is_player(other) {
+            continue;
+        }
+
+        if let Ok((mut state, mut interactable)) = doors.get_mut(door_trigger.door) {
+            *state = next;
+            interactable.verb = next.verb().to_string();
+        }
+    }
+}
+
+fn move_sliding_doors(
+    time: Res<Time>,
+    mut doors: Query<(&mut SlidingDoor, &DoorState, &Position, &mut LinearVelocity)>,
+) {
+    for (mut door, state, position, mut velocity) in doors.iter_mut() {
+        let closed = *door.closed_position.get_or_insert(position.0);
+
+        let target = match state {
+            DoorState::Closed => closed,
+            DoorState::Open => closed + door.open_offset,
+        };
+
+        let offset = target - position.0;
+        velocity.0 = match offset.length() <= door.speed * time.delta_secs() {
+            true => Vec3::ZERO,
+            false => offset.normalize() * door.speed,
+        };
+    }
+}
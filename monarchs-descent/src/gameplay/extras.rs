@@ -0,0 +1,174 @@
+//! Turns custom properties set on nodes in Blender into components when a scene is spawned.
+//!
+//! Each property name is either the type name of a reflected component with the value written
+//! as RON, e.g. `Item` = `()` or `RigidBody` = `Dynamic`, or one of the shorthands below.
+//!
+//! - `collider`: `trimesh`, `convex_hull` or `none`, overrides the collider the scene root's
+//!   [`ColliderConstructorHierarchy`] builds for the node's meshes.
+//! - `shadows`: `true` or `false`, toggles shadows on a light exported from Blender.
+
+use avian3d::prelude::{
+    ColliderConstructor, ColliderConstructorHierarchy, ColliderConstructorHierarchyConfig,
+};
+use bevy::ecs::reflect::ReflectCommandExt;
+use bevy::gltf::GltfExtras;
+use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
+use bevy::reflect::serde::TypedReflectDeserializer;
+use bevy::scene::SceneInstanceReady;
+use serde::de::DeserializeSeed;
+use serde_json::Value;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(apply_gltf_extras);
+}
+
+/// Runs before the scene root's colliders are built, so collider overrides are picked up.
+fn apply_gltf_extras(
+    trigger: Trigger<SceneInstanceReady>,
+    mut commands: Commands,
+    registry: Res<AppTypeRegistry>,
+    children: Query<&Children>,
+    extras: Query<&GltfExtras>,
+    names: Query<&Name, With<Mesh3d>>,
+    mut hierarchies: Query<&mut ColliderConstructorHierarchy>,
+    mut lights: Query<AnyOf<(&mut PointLight, &mut SpotLight, &mut DirectionalLight)>>,
+) {
+    let root = trigger.target();
+    let registry = registry.read();
+    let mut hierarchy = hierarchies.get_mut(root).ok();
+
+    for node in children.iter_descendants(root) {
+        let Ok(extras) = extras.get(node) else {
+            continue;
+        };
+
+        let properties = match serde_json::from_str(&extras.value) {
+            Ok(Value::Object(properties)) => properties,
+            Ok(_) => {
+                warn!(?node, value = extras.value, "glTF extras are not an object");
+                continue;
+            }
+            Err(error) => {
+                warn!(?node, %error, "failed to parse glTF extras");
+                continue;
+            }
+        };
+
+        for (key, value) in properties {
+            let value = match value {
+                Value::String(value) => value,
+                value => value.to_string(),
+            };
+
+            match key.as_str() {
+                "collider" => {
+                    let Some(hierarchy) = hierarchy.as_deref_mut() else {
+                        warn!(
+                            ?node,
+                            "collider override in a scene without a collider hierarchy"
+                        );
+                        continue;
+                    };
+                    let meshes = std::iter::once(node).chain(children.iter_descendants(node));
+                    override_collider(
+                        hierarchy,
+                        meshes.filter_map(|mesh| names.get(mesh).ok()),
+                        &value,
+                    );
+                }
+                "shadows" => {
+                    let Ok(shadows) = value.parse::<bool>() else {
+                        warn!(?node, value, "shadows must be true or false");
+                        continue;
+                    };
+                    let Ok((point, spot, directional)) = lights.get_mut(node) else {
+                        warn!(?node, "shadows set on a node without a light");
+                        continue;
+                    };
+                    if let Some(mut light) = point {
+                        light.shadows_enabled = shadows;
+                    }
+                    if let Some(mut light) = spot {
+                        light.shadows_enabled = shadows;
+                    }
+                    if let Some(mut light) = directional {
+                        light.shadows_enabled = shadows;
+                    }
+                }
+                _ => insert_component(&mut commands, &registry, node, &key, &value),
+            }
+        }
+    }
+}
+
+/// Meshes are matched by name, so meshes sharing a name share the override.
+fn override_collider<'a>(
+    hierarchy: &mut ColliderConstructorHierarchy,
+    meshes: impl Iterator<Item = &'a Name>,
+    value: &str,
+) {
+    let constructor = match value {
+        "trimesh" => Some(ColliderConstructor::TrimeshFromMesh),
+        "convex_hull" => Some(ColliderConstructor::ConvexHullFromMesh),
+        "none" => None,
+        _ => {
+            warn!(
+                value,
+                "unknown collider, expected trimesh, convex_hull or none"
+            );
+            return;
+        }
+    };
+
+    for name in meshes {
+        let config = constructor
+            .clone()
+            .map(|constructor| ColliderConstructorHierarchyConfig {
+                constructor: Some(constructor),
+                ..default()
+            });
+        hierarchy.config.insert(name.to_string(), config);
+    }
+}
+
+fn insert_component(
+    commands: &mut Commands,
+    registry: &TypeRegistry,
+    entity: Entity,
+    key: &str,
+    value: &str,
+) {
+    let Some(registration) = registry
+        .get_with_short_type_path(key)
+        .or_else(|| registry.get_with_type_path(key))
+    else {
+        debug!(key, "glTF extra is not a registered type, ignoring");
+        return;
+    };
+    if registration.data::<ReflectComponent>().is_none() {
+        warn!(key, "glTF extra is not a component");
+        return;
+    }
+
+    // Marker components have nothing to set, so an empty property stands for `()`.
+    let value = match value.trim() {
+        "" => "()",
+        value => value,
+    };
+
+    let mut deserializer = match ron::Deserializer::from_str(value) {
+        Ok(deserializer) => deserializer,
+        Err(error) => {
+            warn!(key, value, %error, "failed to parse glTF extra");
+            return;
+        }
+    };
+    match TypedReflectDeserializer::new(registration, registry).deserialize(&mut deserializer) {
+        Ok(component) => {
+            debug!(?entity, key, "inserting component from glTF extras");
+            commands.entity(entity).insert_reflect(component);
+        }
+        Err(error) => warn!(key, value, %error, "failed to deserialize glTF extra"),
+    }
+}
@@ -0,0 +1,205 @@
+use bevy::pbr::{MaterialPipeline, MaterialPipelineKey, NotShadowCaster};
+use bevy::prelude::*;
+use bevy::render::mesh::MeshVertexBufferLayoutRef;
+use bevy::render::render_resource::{
+    AsBindGroup, Face, RenderPipelineDescriptor, ShaderRef, SpecializedMeshPipelineError,
+};
+use bevy::render::view::RenderLayers;
+
+const OUTLINE_SHADER: &str = "shaders/outline.wgsl";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Highlighted>()
+        .register_type::<OutlineSettings>()
+        .register_type::<OutlineHull>();
+
+    app.add_plugins(MaterialPlugin::<OutlineMaterial>::default());
+
+    app.init_resource::<OutlineSettings>();
+
+    app.add_systems(Startup, setup_outline_materials);
+
+    app.add_systems(
+        Update,
+        (
+            update_outline_materials.run_if(resource_changed::<OutlineSettings>),
+            remove_outlines,
+            add_outlines,
+        )
+            .chain(),
+    );
+}
+
+/// Draws an outline around every mesh of the entity and its descendants.
+#[derive(Default, Component, Reflect, Debug, Copy, Clone, PartialEq)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub enum Highlighted {
+    /// The interaction target under the crosshair.
+    #[default]
+    Focus,
+    /// Loot lying around near the player.
+    Loot,
+}
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct OutlineSettings {
+    pub focus_color: Color,
+    pub loot_color: Color,
+    /// Outline width in world units.
+    pub thickness: f32,
+}
+
+impl Default for OutlineSettings {
+    fn default() -> Self {
+        Self {
+            focus_color: Color::srgb(1.0, 0.85, 0.3),
+            loot_color: Color::srgb(0.4, 0.7, 1.0),
+            thickness: 0.015,
+        }
+    }
+}
+
+/// Unlit inverted hull, the mesh is drawn again with front faces culled and pushed out along
+/// its normals.
+#[derive(Asset, TypePath, AsBindGroup, Clone, Debug)]
+pub struct OutlineMaterial {
+    #[uniform(0)]
+    color: LinearRgba,
+    #[uniform(0)]
+    thickness: f32,
+}
+
+impl Material for OutlineMaterial {
+    fn vertex_shader() -> ShaderRef {
+        OUTLINE_SHADER.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        OUTLINE_SHADER.into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        descriptor.vertex.buffers = vec![layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
+        ])?];
+        descriptor.primitive.cull_mode = Some(Face::Front);
+        Ok(())
+    }
+}
+
+#[derive(Resource, Debug)]
+struct OutlineMaterials {
+    focus: Handle<OutlineMaterial>,
+    loot: Handle<OutlineMaterial>,
+}
+
+impl OutlineMaterials {
+    fn get(&self, highlighted: Highlighted) -> Handle<OutlineMaterial> {
+        match highlighted {
+            Highlighted::Focus => self.focus.clone(),
+            Highlighted::Loot => self.loot.clone(),
+        }
+    }
+}
+
+/// Outline copy of a mesh belonging to the highlighted `owner`.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct OutlineHull {
+    owner: Entity,
+}
+
+fn outline_material(color: Color, thickness: f32) -> OutlineMaterial {
+    OutlineMaterial {
+        color: color.to_linear(),
+        thickness,
+    }
+}
+
+fn setup_outline_materials(
+    mut commands: Commands,
+    settings: Res<OutlineSettings>,
+    mut materials: ResMut<Assets<OutlineMaterial>>,
+) {
+    commands.insert_resource(OutlineMaterials {
+        focus: materials.add(outline_material(settings.focus_color, settings.thickness)),
+        loot: materials.add(outline_material(settings.loot_color, settings.thickness)),
+    });
+}
+
+fn update_outline_materials(
+    settings: Res<OutlineSettings>,
+    handles: Res<OutlineMaterials>,
+    mut materials: ResMut<Assets<OutlineMaterial>>,
+) {
+    for (handle, color) in [
+        (&handles.focus, settings.focus_color),
+        (&handles.loot, settings.loot_color),
+    ] {
+        if let Some(material) = materials.get_mut(handle) {
+            *material = outline_material(color, settings.thickness);
+        }
+    }
+}
+
+fn remove_outlines(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Highlighted>,
+    changed: Query<Entity, Changed<Highlighted>>,
+    hulls: Query<(Entity, &OutlineHull)>,
+) {
+    let owners: Vec<Entity> = removed.read().chain(changed.iter()).collect();
+    if owners.is_empty() {
+        return;
+    }
+
+    for (hull, outline) in hulls.iter() {
+        if owners.contains(&outline.owner) {
+            commands.entity(hull).try_despawn();
+        }
+    }
+}
+
+/// Each hull copies the render layers of its mesh, so view model meshes are only outlined by
+/// the view model camera and nothing ends up on the UI layer.
+fn add_outlines(
+    mut commands: Commands,
+    highlighted: Query<(Entity, &Highlighted), Changed<Highlighted>>,
+    children: Query<&Children>,
+    meshes: Query<(&Mesh3d, Option<&RenderLayers>), Without<OutlineHull>>,
+    materials: Option<Res<OutlineMaterials>>,
+) {
+    let Some(materials) = materials else {
+        return;
+    };
+
+    for (owner, kind) in highlighted.iter() {
+        let material = materials.get(*kind);
+
+        for entity in std::iter::once(owner).chain(children.iter_descendants(owner)) {
+            let Ok((mesh, layers)) = meshes.get(entity) else {
+                continue;
+            };
+
+            let hull = commands
+                .spawn((
+                    Name::new("Outline"),
+                    OutlineHull { owner },
+                    Mesh3d(mesh.0.clone()),
+                    MeshMaterial3d(material.clone()),
+                    NotShadowCaster,
+                    layers.cloned().unwrap_or_default(),
+                ))
+                .id();
+            commands.entity(entity).add_child(hull);
+        }
+    }
+}
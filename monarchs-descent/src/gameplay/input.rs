@@ -1,23 +1,217 @@
+use crate::gameplay::settings::bindings::{
+    BindInputs, BindingContext, CapturedInput, InputBinding, InputBindings, RebindAppExt,
+};
+use crate::gameplay::settings::config::{ControlSettings, GameSettings};
 use bevy::prelude::*;
 use bevy::window::CursorGrabMode;
 use bevy_enhanced_input::prelude::*;
 
-const DEFAULT_SENSITIVITY: f32 = 0.002;
+const MENU_UP: (KeyCode, KeyCode, GamepadButton) =
+    (KeyCode::ArrowUp, KeyCode::KeyW, GamepadButton::DPadUp);
+const MENU_DOWN: (KeyCode, KeyCode, GamepadButton) =
+    (KeyCode::ArrowDown, KeyCode::KeyS, GamepadButton::DPadDown);
+const MENU_LEFT: (KeyCode, KeyCode, GamepadButton) =
+    (KeyCode::ArrowLeft, KeyCode::KeyA, GamepadButton::DPadLeft);
+const MENU_RIGHT: (KeyCode, KeyCode, GamepadButton) =
+    (KeyCode::ArrowRight, KeyCode::KeyD, GamepadButton::DPadRight);
+const CONFIRM_KEY: KeyCode = KeyCode::Space;
+const CLOSE_MENU_BUTTON: GamepadButton = GamepadButton::West;
+const FLY_UP_BUTTON: GamepadButton = GamepadButton::RightTrigger;
+const FLY_DOWN_BUTTON: GamepadButton = GamepadButton::LeftTrigger;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_input_context::<PlayerActions>()
         .add_input_context::<InventoryActions>()
+        .add_input_context::<PauseActions>()
         .add_input_context::<SettingsActions>();
 
+    app.add_rebindable::<MoveForward>(
+        BindingContext::Player,
+        "Move forward",
+        InputBinding::keyboard(KeyCode::KeyW),
+    )
+    .add_rebindable::<MoveBack>(
+        BindingContext::Player,
+        "Move back",
+        InputBinding::keyboard(KeyCode::KeyS),
+    )
+    .add_rebindable::<MoveLeft>(
+        BindingContext::Player,
+        "Move left",
+        InputBinding::keyboard(KeyCode::KeyA),
+    )
+    .add_rebindable::<MoveRight>(
+        BindingContext::Player,
+        "Move right",
+        InputBinding::keyboard(KeyCode::KeyD),
+    )
+    .add_rebindable::<Jump>(
+        BindingContext::Player,
+        "Jump / fly up",
+        InputBinding::new(KeyCode::Space, GamepadButton::South),
+    )
+    .add_rebindable::<Crouch>(
+        BindingContext::Player,
+        "Crouch / fly down",
+        InputBinding::new(KeyCode::ControlLeft, GamepadButton::RightThumb),
+    )
+    .add_rebindable::<Sprint>(
+        BindingContext::Player,
+        "Sprint",
+        InputBinding::new(KeyCode::ShiftLeft, GamepadButton::LeftThumb),
+    )
+    .add_rebindable::<Interact>(
+        BindingContext::Player,
+        "Interact",
+        InputBinding::new(KeyCode::KeyF, GamepadButton::North),
+    )
+    .add_rebindable::<OpenInventory>(
+        BindingContext::Player,
+        "Inventory",
+        InputBinding::new(KeyCode::KeyE, GamepadButton::East),
+    )
+    .add_rebindable::<Pause>(
+        BindingContext::Player,
+        "Pause",
+        InputBinding::new(KeyCode::Escape, GamepadButton::Start),
+    )
+    .add_rebindable::<ToggleCameraMode>(
+        BindingContext::Player,
+        "Toggle camera",
+        InputBinding::new(KeyCode::KeyV, GamepadButton::DPadUp),
+    )
+    .add_rebindable::<SwapShoulder>(
+        BindingContext::Player,
+        "Swap shoulder",
+        InputBinding::new(KeyCode::KeyX, GamepadButton::DPadRight),
+    )
+    .add_rebindable::<ToggleNoclip>(
+        BindingContext::Player,
+        "Noclip",
+        InputBinding::keyboard(KeyCode::KeyN),
+    )
+    .add_rebindable::<ToggleSpectator>(
+        BindingContext::Player,
+        "Spectator",
+        InputBinding::keyboard(KeyCode::KeyM),
+    )
+    .add_rebindable::<CloseInventory>(
+        BindingContext::Inventory,
+        "Close inventory",
+        InputBinding::new(KeyCode::KeyE, GamepadButton::East),
+    )
+    .add_rebindable::<Resume>(
+        BindingContext::Pause,
+        "Resume",
+        InputBinding::new(KeyCode::Escape, GamepadButton::Start),
+    )
+    .add_rebindable::<CloseSettings>(
+        BindingContext::Settings,
+        "Close menu",
+        InputBinding::new(KeyCode::Escape, GamepadButton::Start),
+    )
+    .add_rebindable::<MenuConfirm>(
+        BindingContext::Settings,
+        "Confirm",
+        InputBinding::new(KeyCode::Enter, GamepadButton::South),
+    )
+    .add_rebindable::<NextTab>(
+        BindingContext::Settings,
+        "Next tab",
+        InputBinding::new(KeyCode::KeyE, GamepadButton::RightTrigger),
+    )
+    .add_rebindable::<PreviousTab>(
+        BindingContext::Settings,
+        "Previous tab",
+        InputBinding::new(KeyCode::KeyQ, GamepadButton::LeftTrigger),
+    );
+
+    // The fixed inputs bound in the binding observers below, kept out of reach of rebinding.
+    app.reserve_inputs::<FlyUp>(
+        BindingContext::Player,
+        "Fly up",
+        [CapturedInput::Button(FLY_UP_BUTTON)],
+    )
+    .reserve_inputs::<FlyDown>(
+        BindingContext::Player,
+        "Fly down",
+        [CapturedInput::Button(FLY_DOWN_BUTTON)],
+    )
+    .reserve_inputs::<CloseSettings>(
+        BindingContext::Settings,
+        "Close menu",
+        [CapturedInput::Button(CLOSE_MENU_BUTTON)],
+    )
+    .reserve_inputs::<MenuConfirm>(
+        BindingContext::Settings,
+        "Confirm",
+        [CapturedInput::Key(CONFIRM_KEY)],
+    )
+    .reserve_inputs::<MenuUp>(BindingContext::Settings, "Menu up", menu_inputs(MENU_UP))
+    .reserve_inputs::<MenuDown>(
+        BindingContext::Settings,
+        "Menu down",
+        menu_inputs(MENU_DOWN),
+    )
+    .reserve_inputs::<MenuLeft>(
+        BindingContext::Settings,
+        "Menu left",
+        menu_inputs(MENU_LEFT),
+    )
+    .reserve_inputs::<MenuRight>(
+        BindingContext::Settings,
+        "Menu right",
+        menu_inputs(MENU_RIGHT),
+    );
+
     app.add_observer(binding_player)
         .add_observer(binding_inventory)
+        .add_observer(binding_pause)
         .add_observer(binding_settings);
+
+    app.add_systems(
+        Update,
+        rebuild_bindings.run_if(resource_changed::<GameSettings>),
+    );
+}
+
+fn menu_inputs((arrow, key, button): (KeyCode, KeyCode, GamepadButton)) -> [CapturedInput; 3] {
+    [
+        CapturedInput::Key(arrow),
+        CapturedInput::Key(key),
+        CapturedInput::Button(button),
+    ]
+}
+
+/// Bindings only depend on the control settings, moving a volume or video slider leaves them be.
+fn rebuild_bindings(
+    mut commands: Commands,
+    settings: Res<GameSettings>,
+    mut previous: Local<Option<ControlSettings>>,
+) {
+    if previous.as_ref() == Some(&settings.controls) {
+        return;
+    }
+
+    *previous = Some(settings.controls.clone());
+    commands.trigger(RebuildBindings);
+}
+
+/// Mouse look modifiers from the player's sensitivity and invert Y settings.
+fn look_modifiers(settings: &GameSettings) -> (Negate, Scale) {
+    let negate = match settings.controls.invert_y {
+        true => Negate::x(),
+        false => Negate::all(),
+    };
+    (negate, Scale::splat(settings.controls.sensitivity))
 }
 
 fn binding_player(
     trigger: Trigger<Binding<PlayerActions>>,
     mut on_foot: Query<&mut Actions<PlayerActions>>,
     mut window: Single<&mut Window>,
+    settings: Res<GameSettings>,
+    bindings: InputBindings,
 ) {
     let mut actions = on_foot.get_mut(trigger.target()).unwrap();
 
@@ -27,38 +221,102 @@ fn binding_player(
     actions
         .bind::<Rotate>()
         .to((Input::mouse_motion(), Axial::right_stick()))
-        .with_modifiers((Negate::all(), Scale::splat(DEFAULT_SENSITIVITY)));
+        .with_modifiers(look_modifiers(&settings));
+
+    let movement = [
+        bindings.get::<MoveForward>(),
+        bindings.get::<MoveRight>(),
+        bindings.get::<MoveBack>(),
+        bindings.get::<MoveLeft>(),
+    ]
+    .map(|binding| binding.keyboard);
+
+    match movement {
+        [Some(north), Some(east), Some(south), Some(west)] => {
+            actions
+                .bind::<Move>()
+                .to((
+                    Cardinal {
+                        north,
+                        east,
+                        south,
+                        west,
+                    },
+                    Axial::left_stick(),
+                ))
+                .with_modifiers((DeadZone::default(), SmoothNudge::default()));
+        }
+        _ => {
+            warn!("movement keys are partially unbound, only the stick moves");
+            actions
+                .bind::<Move>()
+                .to(Axial::left_stick())
+                .with_modifiers((DeadZone::default(), SmoothNudge::default()));
+        }
+    }
+
+    actions.bind::<Jump>().to_binding(bindings.get::<Jump>());
 
     actions
-        .bind::<Move>()
-        .to((Cardinal::wasd_keys(), Axial::left_stick()))
-        .with_modifiers((DeadZone::default(), SmoothNudge::default()));
-
-    actions
-        .bind::<Jump>()
-        .to((KeyCode::Space, GamepadButton::South));
+        .bind::<Crouch>()
+        .to_binding(bindings.get::<Crouch>());
 
     actions
-        .bind::<Crouch>()
-        .to((KeyCode::ControlLeft, GamepadButton::RightThumb));
+        .bind::<Sprint>()
+        .to_binding(bindings.get::<Sprint>());
 
     actions
         .bind::<OpenInventory>()
-        .to((KeyCode::KeyE, GamepadButton::East));
+        .to_binding(bindings.get::<OpenInventory>());
 
     actions
         .bind::<Interact>()
-        .to((KeyCode::KeyF, GamepadButton::North));
+        .to_binding(bindings.get::<Interact>());
+
+    // Shares the interact key, the hold condition separates a tap from a held press and
+    // the per target duration is checked against the elapsed time.
+    actions
+        .bind::<HoldInteract>()
+        .to_binding(bindings.get::<Interact>())
+        .with_conditions(Hold::new(0.1));
+
+    actions.bind::<Pause>().to_binding(bindings.get::<Pause>());
 
     actions
-        .bind::<OpenSettings>()
-        .to((KeyCode::Escape, GamepadButton::Start));
+        .bind::<ToggleCameraMode>()
+        .to_binding(bindings.get::<ToggleCameraMode>());
+
+    actions
+        .bind::<SwapShoulder>()
+        .to_binding(bindings.get::<SwapShoulder>());
+
+    // Flying reuses the jump and crouch keys, they are never active at the same time. The
+    // gamepad flies on the triggers, which are free while the sticks steer.
+    actions.bind::<FlyUp>().to_binding(InputBinding {
+        keyboard: bindings.get::<Jump>().keyboard,
+        gamepad: Some(FLY_UP_BUTTON),
+    });
+
+    actions.bind::<FlyDown>().to_binding(InputBinding {
+        keyboard: bindings.get::<Crouch>().keyboard,
+        gamepad: Some(FLY_DOWN_BUTTON),
+    });
+
+    actions
+        .bind::<ToggleNoclip>()
+        .to_binding(bindings.get::<ToggleNoclip>());
+
+    actions
+        .bind::<ToggleSpectator>()
+        .to_binding(bindings.get::<ToggleSpectator>());
 }
 
 fn binding_inventory(
     trigger: Trigger<Binding<InventoryActions>>,
     mut inventory: Query<&mut Actions<InventoryActions>>,
     mut window: Single<&mut Window>,
+    settings: Res<GameSettings>,
+    bindings: InputBindings,
 ) {
     let mut actions = inventory.get_mut(trigger.target()).unwrap();
 
@@ -68,28 +326,81 @@ fn binding_inventory(
     actions
         .bind::<UiMove>()
         .to((Input::mouse_motion(), Axial::right_stick()))
-        .with_modifiers((Negate::all(), Scale::splat(DEFAULT_SENSITIVITY)));
+        .with_modifiers(look_modifiers(&settings));
 
     actions
         .bind::<CloseInventory>()
-        .to((KeyCode::KeyE, GamepadButton::East));
+        .to_binding(bindings.get::<CloseInventory>());
+}
+
+fn binding_pause(
+    trigger: Trigger<Binding<PauseActions>>,
+    mut pause: Query<&mut Actions<PauseActions>>,
+    mut window: Single<&mut Window>,
+    bindings: InputBindings,
+) {
+    let mut actions = pause.get_mut(trigger.target()).unwrap();
+
+    window.cursor_options.grab_mode = CursorGrabMode::None;
+    window.cursor_options.visible = true;
+
+    actions
+        .bind::<Resume>()
+        .to_binding(bindings.get::<Resume>());
 }
 
 fn binding_settings(
     trigger: Trigger<Binding<SettingsActions>>,
     mut setttings: Query<&mut Actions<SettingsActions>>,
     mut window: Single<&mut Window>,
+    bindings: InputBindings,
 ) {
     let mut actions = setttings.get_mut(trigger.target()).unwrap();
 
     window.cursor_options.grab_mode = CursorGrabMode::None;
     window.cursor_options.visible = true;
 
-    actions.bind::<CloseSettings>().to((
-        KeyCode::Escape,
-        GamepadButton::Start,
-        GamepadButton::West,
-    ));
+    // The fixed inputs next to the rebindable ones, and all of navigation, stay bound so
+    // rebinding can never lock the player out of the menu.
+    actions
+        .bind::<CloseSettings>()
+        .to_binding(bindings.get::<CloseSettings>())
+        .to(CLOSE_MENU_BUTTON);
+
+    actions.bind::<MenuUp>().to(MENU_UP);
+
+    actions.bind::<MenuDown>().to(MENU_DOWN);
+
+    actions.bind::<MenuLeft>().to(MENU_LEFT);
+
+    actions.bind::<MenuRight>().to(MENU_RIGHT);
+
+    actions
+        .bind::<MenuConfirm>()
+        .to_binding(bindings.get::<MenuConfirm>())
+        .to(CONFIRM_KEY);
+
+    actions
+        .bind::<NextTab>()
+        .to_binding(bindings.get::<NextTab>());
+
+    actions
+        .bind::<PreviousTab>()
+        .to_binding(bindings.get::<PreviousTab>());
+}
+
+/// Short human readable name for a key, for use in on-screen prompts.
+pub(super) fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+/// Short human readable name for a gamepad button.
+pub(super) fn button_label(button: GamepadButton) -> String {
+    format!("{button:?}")
 }
 
 #[derive(InputContext, Debug)]
@@ -98,6 +409,9 @@ pub(super) struct PlayerActions;
 #[derive(InputContext, Debug)]
 pub(super) struct InventoryActions;
 
+#[derive(InputContext, Debug)]
+pub(super) struct PauseActions;
+
 #[derive(InputContext, Debug)]
 pub(super) struct SettingsActions;
 
@@ -109,10 +423,27 @@ pub(super) struct Jump;
 #[input_action(output = bool)]
 pub(super) struct Crouch;
 
+#[derive(InputAction, Debug)]
+#[input_action(output = bool)]
+pub(super) struct Sprint;
+
 #[derive(InputAction, Debug)]
 #[input_action(output = Vec2)]
 pub(super) struct Move;
 
+/// Binding table keys for the keyboard directions of [`Move`].
+#[derive(Debug)]
+pub(super) struct MoveForward;
+
+#[derive(Debug)]
+pub(super) struct MoveBack;
+
+#[derive(Debug)]
+pub(super) struct MoveLeft;
+
+#[derive(Debug)]
+pub(super) struct MoveRight;
+
 #[derive(InputAction, Debug)]
 #[input_action(output = Vec2)]
 pub(super) struct Rotate;
@@ -135,8 +466,68 @@ pub(super) struct Interact;
 
 #[derive(InputAction, Debug)]
 #[input_action(output = bool, require_reset = true)]
-pub(super) struct OpenSettings;
+pub(super) struct HoldInteract;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct Pause;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct Resume;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct ToggleCameraMode;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct SwapShoulder;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool)]
+pub(super) struct FlyUp;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool)]
+pub(super) struct FlyDown;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct ToggleNoclip;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct ToggleSpectator;
 
 #[derive(InputAction, Debug)]
 #[input_action(output = bool, require_reset = true)]
 pub(super) struct CloseSettings;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct MenuUp;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct MenuDown;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct MenuLeft;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct MenuRight;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct MenuConfirm;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct NextTab;
+
+#[derive(InputAction, Debug)]
+#[input_action(output = bool, require_reset = true)]
+pub(super) struct PreviousTab;
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Interactable>();
+
+    app.add_observer(start_cooldown);
+
+    app.add_systems(Update, tick_cooldowns);
+}
+
+/// Something an actor can focus and interact with. Gameplay reacts by observing [`Interacted`]
+/// on the entity.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Interactable {
+    /// What interacting does, shown in prompts as "Press F to <verb> <label>".
+    pub verb: String,
+    /// Overrides the entity [`Name`] in prompts.
+    pub label: Option<String>,
+    /// Seconds `Interact` has to be held before the interaction fires.
+    pub hold: Option<f32>,
+    pub cooldown: f32,
+    pub enabled: bool,
+    remaining_cooldown: f32,
+}
+
+impl Default for Interactable {
+    fn default() -> Self {
+        Self::new("use")
+    }
+}
+
+impl Interactable {
+    pub fn new(verb: impl Into<String>) -> Self {
+        Self {
+            verb: verb.into(),
+            label: None,
+            hold: None,
+            cooldown: 0.0,
+            enabled: true,
+            remaining_cooldown: 0.0,
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_hold(mut self, seconds: f32) -> Self {
+        self.hold = Some(seconds);
+        self
+    }
+
+    pub fn with_cooldown(mut self, seconds: f32) -> Self {
+        self.cooldown = seconds;
+        self
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.enabled && self.remaining_cooldown <= 0.0
+    }
+
+    pub fn label<'a>(&'a self, name: Option<&'a Name>) -> &'a str {
+        self.label
+            .as_deref()
+            .or(name.map(Name::as_str))
+            .unwrap_or("object")
+    }
+}
+
+/// Triggered on the target entity when `actor` interacts with it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Interacted {
+    pub actor: Entity,
+    pub target: Entity,
+}
+
+fn start_cooldown(trigger: Trigger<Interacted>, mut interactables: Query<&mut Interactable>) {
+    if let Ok(mut interactable) = interactables.get_mut(trigger.target()) {
+        interactable.remaining_cooldown = interactable.cooldown;
+    }
+}
+
+fn tick_cooldowns(time: Res<Time>, mut interactables: Query<&mut Interactable>) {
+    for mut interactable in interactables.iter_mut() {
+        if interactable.remaining_cooldown > 0.0 {
+            interactable.remaining_cooldown -= time.delta_secs();
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::gameplay::interactable::Interactable;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Container>();
+}
+
+/// A chest or locker in the world, its contents are the [`Inventory`] on the same entity.
+///
+/// [`Inventory`]: crate::gameplay::items::inventory::Inventory
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(Interactable::new("open"))]
+pub struct Container;
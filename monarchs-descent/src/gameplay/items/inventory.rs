@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use std::iter;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Inventory>().register_type::<EquippedItem>();
@@ -42,15 +43,45 @@ impl Inventory {
         &self.items
     }
 
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count >= self.capacity
+    }
+
     pub fn insert_item(&mut self, item: Entity) -> Result<(), ()> {
         for mut position in self.items.iter_mut() {
             if position.is_none() {
                 *position = Some(item);
+                self.count += 1;
                 return Ok(());
             }
         }
         Err(())
     }
+
+    pub fn remove_item(&mut self, slot: usize) -> Option<Entity> {
+        let item = self.items.get_mut(slot)?.take();
+        if item.is_some() {
+            self.count -= 1;
+        }
+        item
+    }
+
+    /// Packs the items to the front of the inventory, ordered by `key`.
+    pub fn sort_by_key<K: Ord>(&mut self, key: impl FnMut(&Entity) -> K) {
+        let mut items: Vec<Entity> = self.items.iter().flatten().copied().collect();
+        items.sort_by_cached_key(key);
+
+        self.items = items
+            .into_iter()
+            .map(Some)
+            .chain(iter::repeat(None))
+            .take(self.capacity)
+            .collect();
+    }
 }
 
 #[derive(Component, Debug, Reflect)]
@@ -1,13 +1,19 @@
+pub(super) mod container;
 pub(super) mod inventory;
+pub(super) mod pickup;
 
+use crate::gameplay::interactable::Interactable;
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Item>();
 
-    app.add_plugins(inventory::plugin);
+    app.add_plugins(inventory::plugin)
+        .add_plugins(container::plugin)
+        .add_plugins(pickup::plugin);
 }
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
+#[require(Interactable::new("pick up"))]
 pub struct Item;
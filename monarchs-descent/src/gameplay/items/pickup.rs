@@ -0,0 +1,76 @@
+use crate::gameplay::highlight::Highlighted;
+use crate::gameplay::interactable::{Interactable, Interacted};
+use crate::gameplay::items::Item;
+use crate::gameplay::items::inventory::Inventory;
+use crate::gameplay::player::Player;
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+/// Items on the ground within this distance of the player get a [`Highlighted::Loot`] outline.
+const LOOT_HIGHLIGHT_RANGE: f32 = 8.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(pick_up_item);
+
+    app.add_systems(Update, highlight_nearby_loot);
+}
+
+fn pick_up_item(
+    trigger: Trigger<Interacted>,
+    mut commands: Commands,
+    mut inventories: Query<&mut Inventory>,
+    items: Query<Option<&RigidBodyColliders>, With<Item>>,
+) {
+    let Interacted { actor, target } = *trigger.event();
+
+    let Ok(colliders) = items.get(target) else {
+        return;
+    };
+    let Ok(mut inventory) = inventories.get_mut(actor) else {
+        return;
+    };
+
+    if inventory.insert_item(target).is_err() {
+        debug!(?actor, "inventory full");
+        return;
+    }
+
+    commands
+        .entity(target)
+        .remove::<(Interactable, Highlighted)>()
+        .insert((Visibility::Hidden, RigidBodyDisabled));
+
+    if let Some(colliders) = colliders {
+        for collider in colliders.iter() {
+            commands.entity(collider).insert(ColliderDisabled);
+        }
+    }
+}
+
+/// Whether an item at `item` gets a [`Highlighted::Loot`] outline while nothing else highlights it.
+pub(in crate::gameplay) fn in_loot_range(item: Vec3, player: Vec3) -> bool {
+    item.distance(player) <= LOOT_HIGHLIGHT_RANGE
+}
+
+fn highlight_nearby_loot(
+    mut commands: Commands,
+    player: Single<&GlobalTransform, With<Player>>,
+    items: Query<
+        (Entity, &GlobalTransform, Option<&Highlighted>),
+        (With<Item>, With<Interactable>),
+    >,
+) {
+    for (item, transform, highlighted) in items.iter() {
+        let nearby = in_loot_range(transform.translation(), player.translation());
+
+        match highlighted {
+            None if nearby => {
+                commands.entity(item).insert(Highlighted::Loot);
+            }
+            Some(Highlighted::Loot) if !nearby => {
+                commands.entity(item).remove::<Highlighted>();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::gameplay::items::inventory::Inventory;
+use crate::gameplay::player::Player;
+use crate::level::{Levels, LoadLevel};
+use crate::{AppState, GameState};
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<LevelExit>();
+
+    app.init_resource::<CarriedInventory>();
+
+    app.add_observer(leave_level)
+        .add_observer(restore_carried_inventory);
+
+    app.add_systems(Update, use_level_exits.run_if(in_state(GameState::Playing)))
+        .add_systems(OnEnter(AppState::MainMenu), drop_carried_inventory);
+}
+
+/// Sensor that loads `level` when the player walks into it, placed in Blender through glTF
+/// extras as `LevelExit` = `(level: "basic_surface")`.
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component, Default)]
+#[require(Sensor, CollisionEventsEnabled)]
+pub struct LevelExit {
+    pub level: String,
+}
+
+/// Triggered to move the player to another level, their inventory comes along.
+#[derive(Event, Debug, Clone)]
+pub struct LeaveLevel {
+    pub level: String,
+}
+
+/// Player's inventory while the next level loads, its items are kept out of the level teardown.
+#[derive(Resource, Default, Debug)]
+struct CarriedInventory(Option<Inventory>);
+
+fn use_level_exits(
+    mut commands: Commands,
+    mut started: EventReader<CollisionStarted>,
+    exits: Query<&LevelExit>,
+    colliders: Query<&ColliderOf>,
+    players: Query<(), With<Player>>,
+) {
+    for CollisionStarted(a, b) in started.read() {
+        let (exit, other) = match (exits.get(*a), exits.get(*b)) {
+            (Ok(exit), _) => (exit, *b),
+            (_, Ok(exit)) => (exit, *a),
+            _ => continue,
+        };
+
+        let body = colliders
+            .get(other)
+            .map_or(other, |collider_of| collider_of.body);
+        if players.contains(body) {
+            commands.trigger(LeaveLevel {
+                level: exit.level.clone(),
+            });
+            return;
+        }
+    }
+}
+
+fn leave_level(
+    trigger: Trigger<LeaveLevel>,
+    mut commands: Commands,
+    player: Single<(Entity, &mut Inventory), With<Player>>,
+    levels: Res<Levels>,
+    mut carried: ResMut<CarriedInventory>,
+) {
+    let level = &trigger.event().level;
+    let (player, mut inventory) = player.into_inner();
+
+    // Already on the way out, taking the inventory again would leave the carried one behind.
+    if carried.0.is_some() {
+        return;
+    }
+    // Checked before the inventory is taken, so a bad exit doesn't empty it.
+    if levels.get(level).is_none() {
+        warn!(level, "leaving to an unknown level");
+        return;
+    }
+
+    let capacity = inventory.capacity();
+    let inventory = std::mem::replace(&mut *inventory, Inventory::new(capacity));
+    for item in inventory.get_items().iter().flatten() {
+        commands.entity(*item).remove::<StateScoped<AppState>>();
+    }
+
+    debug!(?player, level, "leaving level");
+
+    carried.0 = Some(inventory);
+    commands.queue(LoadLevel(level.clone()));
+}
+
+fn restore_carried_inventory(
+    trigger: Trigger<OnAdd, Player>,
+    mut commands: Commands,
+    mut carried: ResMut<CarriedInventory>,
+) {
+    let Some(inventory) = carried.0.take() else {
+        return;
+    };
+
+    for item in inventory.get_items().iter().flatten() {
+        commands
+            .entity(*item)
+            .insert(StateScoped(AppState::Gameplay));
+    }
+
+    commands.entity(trigger.target()).insert(inventory);
+}
+
+/// Loading failed and the game went back to the menu, the carried items have nowhere to go.
+fn drop_carried_inventory(mut commands: Commands, mut carried: ResMut<CarriedInventory>) {
+    let Some(inventory) = carried.0.take() else {
+        return;
+    };
+
+    for item in inventory.get_items().iter().flatten() {
+        commands.entity(*item).despawn();
+    }
+}
@@ -1,62 +1,234 @@
+pub(crate) mod day_night;
+mod door;
+mod extras;
+mod highlight;
 mod input;
+mod interactable;
 mod items;
+mod level_exit;
+mod pause;
+mod platform;
 mod player;
-mod settings;
+pub(crate) mod settings;
+mod spawn;
+pub(crate) mod streaming;
+pub(crate) mod terrain;
+mod transition;
+pub(crate) mod trigger_volume;
 
+use crate::AppState;
+use crate::gameplay::day_night::Sun;
+use crate::gameplay::door::{DoorKey, DoorLock, DoorTrigger, SlidingDoor, spawn_hinged_door};
 use crate::gameplay::input::*;
 use crate::gameplay::items::Item;
+use crate::gameplay::items::container::Container;
+use crate::gameplay::items::inventory::Inventory;
+use crate::gameplay::level_exit::LevelExit;
+use crate::gameplay::platform::{PathMode, PlatformPath};
+use crate::gameplay::spawn::SpawnPoint;
+use crate::gameplay::transition::GameInput;
+use crate::level::{CurrentLevel, FIRST_LEVEL, HIGHLANDS_LEVEL};
+use crate::loading::GameAssets;
 use avian3d::prelude::ColliderConstructor::{ConvexHullFromMesh, TrimeshFromMesh};
-use avian3d::prelude::{ColliderConstructorHierarchy, RigidBody};
-use bevy::pbr::CascadeShadowConfigBuilder;
+use avian3d::prelude::{
+    Collider, ColliderConstructorHierarchy, ColliderConstructorHierarchyReady, RigidBody,
+};
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(input::plugin)
+        .add_plugins(day_night::plugin)
+        .add_plugins(interactable::plugin)
+        .add_plugins(highlight::plugin)
+        .add_plugins(door::plugin)
+        .add_plugins(extras::plugin)
         .add_plugins(player::plugin)
         .add_plugins(items::plugin)
-        .add_plugins(settings::plugin);
+        .add_plugins(level_exit::plugin)
+        .add_plugins(pause::plugin)
+        .add_plugins(platform::plugin)
+        .add_plugins(settings::plugin)
+        .add_plugins(spawn::plugin)
+        .add_plugins(streaming::plugin)
+        .add_plugins(terrain::plugin)
+        .add_plugins(transition::plugin)
+        .add_plugins(trigger_volume::plugin);
 
-    app.add_systems(Startup, setup);
+    app.register_type::<Map>();
+
+    app.add_observer(spawn_test_items);
+
+    app.add_systems(
+        OnEnter(AppState::Gameplay),
+        (
+            setup,
+            spawn_test_props.run_if(|level: Res<CurrentLevel>| level.0 == FIRST_LEVEL),
+        ),
+    );
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(Actions::<SettingsActions>::default());
+/// Root of the level scene, the player is spawned once its colliders have been built.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub(super) struct Map;
+
+fn setup(mut commands: Commands, assets: Res<GameAssets>, level: Res<CurrentLevel>) {
+    commands.spawn((
+        Name::new("Game input"),
+        GameInput,
+        StateScoped(AppState::Gameplay),
+        Actions::<PlayerActions>::default(),
+    ));
 
     commands.spawn((
         Name::new("Light Sun"),
-        Transform::from_xyz(10.0, 10.0, -10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        StateScoped(AppState::Gameplay),
+        Sun,
+        Transform::from_xyz(10.0, 10.0, -10.0),
         DirectionalLight {
             shadows_enabled: true,
             ..default()
         },
-        CascadeShadowConfigBuilder {
-            num_cascades: 1,
-            maximum_distance: 1.6,
-            ..default()
-        }
-        .build(),
     ));
 
-    let map_handle =
-        asset_server.load(GltfAssetLabel::Scene(0).from_asset("maps/basic_surface.glb"));
-
     commands.spawn((
-        Name::new("Map Basic surface"),
-        SceneRoot(map_handle),
+        Name::new(format!("Map {}", level.0)),
+        StateScoped(AppState::Gameplay),
+        Map,
+        SceneRoot(assets.map.clone()),
         ColliderConstructorHierarchy::new(TrimeshFromMesh),
         RigidBody::Static,
     ));
+}
+
+/// Props for trying out gameplay features, only spawned in the first level.
+fn spawn_test_props(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Name::new("Spawn Point"),
+        StateScoped(AppState::Gameplay),
+        SpawnPoint::default(),
+        Transform::default(),
+    ));
 
-    let gun_handle =
-        asset_server.load(GltfAssetLabel::Scene(0).from_asset("weapons/basic_gun.glb"));
+    commands.spawn((
+        Name::new("Platform Test"),
+        StateScoped(AppState::Gameplay),
+        Transform::from_xyz(-5.0, 1.0, 0.0),
+        Mesh3d(meshes.add(Cuboid::new(4.0, 0.5, 4.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.4, 0.4, 0.5))),
+        Collider::cuboid(4.0, 0.5, 4.0),
+        PlatformPath::new(
+            vec![
+                Vec3::new(-5.0, 1.0, 0.0),
+                Vec3::new(-5.0, 1.0, 10.0),
+                Vec3::new(-5.0, 4.0, 10.0),
+            ],
+            2.0,
+        )
+        .with_mode(PathMode::PingPong)
+        .with_wait(1.0),
+    ));
+
+    commands.spawn((
+        Name::new("Chest"),
+        StateScoped(AppState::Gameplay),
+        Transform::from_xyz(3.0, 0.5, -4.0),
+        Mesh3d(meshes.add(Cuboid::new(1.2, 0.8, 0.8))),
+        MeshMaterial3d(materials.add(Color::srgb(0.45, 0.3, 0.15))),
+        Collider::cuboid(1.2, 0.8, 0.8),
+        RigidBody::Static,
+        Container,
+        Inventory::new(12),
+    ));
+
+    let door_material = materials.add(Color::srgb(0.35, 0.35, 0.4));
+
+    let sliding_door = commands
+        .spawn((
+            Name::new("Door Sliding"),
+            StateScoped(AppState::Gameplay),
+            Transform::from_xyz(0.0, 1.5, -10.0),
+            Mesh3d(meshes.add(Cuboid::new(2.0, 3.0, 0.2))),
+            MeshMaterial3d(door_material.clone()),
+            Collider::cuboid(2.0, 3.0, 0.2),
+            SlidingDoor::new(Vec3::X * 2.0, 2.0),
+            DoorLock {
+                key: "red".to_string(),
+            },
+        ))
+        .id();
+
+    commands.spawn((
+        Name::new("Door Sliding Trigger"),
+        StateScoped(AppState::Gameplay),
+        Transform::from_xyz(0.0, 1.5, -10.0),
+        Collider::cuboid(3.0, 3.0, 4.0),
+        DoorTrigger { door: sliding_door },
+    ));
+
+    spawn_hinged_door(
+        &mut commands,
+        Transform::from_xyz(6.0, 1.5, -10.0),
+        Vec3::new(2.0, 3.0, 0.1),
+        meshes.add(Cuboid::new(2.0, 3.0, 0.1)),
+        door_material,
+    );
+
+    commands.spawn((
+        Name::new("Level Exit"),
+        StateScoped(AppState::Gameplay),
+        Transform::from_xyz(-12.0, 1.5, -10.0),
+        Mesh3d(meshes.add(Cuboid::new(2.0, 3.0, 2.0))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgba(0.2, 0.6, 1.0, 0.3),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        })),
+        Collider::cuboid(2.0, 3.0, 2.0),
+        LevelExit {
+            level: HIGHLANDS_LEVEL.to_string(),
+        },
+    ));
+}
+
+/// Loose items fall, so like the player they wait for the map's colliders.
+fn spawn_test_items(
+    trigger: Trigger<ColliderConstructorHierarchyReady>,
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    level: Res<CurrentLevel>,
+    maps: Query<(), With<Map>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !maps.contains(trigger.target()) || level.0 != FIRST_LEVEL {
+        return;
+    }
 
     commands.spawn((
         Name::new("gun"),
+        StateScoped(AppState::Gameplay),
         Transform::from_xyz(5.0, 10.0, 0.0),
-        SceneRoot(gun_handle),
+        SceneRoot(assets.gun.clone()),
         ColliderConstructorHierarchy::new(ConvexHullFromMesh),
         RigidBody::Dynamic,
         Item,
     ));
+
+    commands.spawn((
+        Name::new("Red key"),
+        StateScoped(AppState::Gameplay),
+        Transform::from_xyz(2.0, 1.0, -6.0),
+        Mesh3d(meshes.add(Cuboid::new(0.3, 0.1, 0.15))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.1, 0.1))),
+        Collider::cuboid(0.3, 0.1, 0.15),
+        RigidBody::Dynamic,
+        Item,
+        DoorKey("red".to_string()),
+    ));
 }
@@ -0,0 +1,117 @@
+use crate::gameplay::input::{Pause, Resume};
+use crate::gameplay::transition::RequestGameState;
+use crate::widgets::{menu_button, menu_screen};
+use crate::{AppState, GameState};
+use avian3d::prelude::{Physics, PhysicsTime};
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PausedByMenu>();
+
+    app.add_observer(pause).add_observer(resume);
+
+    app.add_systems(OnEnter(GameState::Paused), (freeze_time, spawn_pause_menu))
+        .add_systems(OnEnter(GameState::Playing), unfreeze_time)
+        .add_systems(OnExit(AppState::Gameplay), unfreeze_time)
+        .add_systems(
+            Update,
+            handle_pause_menu_buttons.run_if(in_state(GameState::Paused)),
+        );
+}
+
+/// Clocks the pause menu paused itself, only these are unpaused again on resume.
+#[derive(Resource, Default, Debug)]
+struct PausedByMenu {
+    time: bool,
+    physics: bool,
+}
+
+#[derive(Component, Debug)]
+enum PauseMenuButton {
+    Resume,
+    Settings,
+    MainMenu,
+    Quit,
+}
+
+fn pause(_trigger: Trigger<Started<Pause>>, mut commands: Commands) {
+    commands.trigger(RequestGameState(GameState::Paused));
+}
+
+fn resume(_trigger: Trigger<Started<Resume>>, mut commands: Commands) {
+    commands.trigger(RequestGameState(GameState::Playing));
+}
+
+/// Time stays frozen in the settings menu, it is only restarted once play resumes.
+fn freeze_time(
+    mut paused_by_menu: ResMut<PausedByMenu>,
+    mut time: ResMut<Time<Virtual>>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    debug!("pausing game");
+
+    // Physics may already be paused from the editor, that pause isn't the menu's to undo. Coming
+    // back from the settings screen finds the clocks paused by the menu itself.
+    paused_by_menu.time |= !time.is_paused();
+    paused_by_menu.physics |= !physics_time.is_paused();
+    time.pause();
+    physics_time.pause();
+}
+
+fn unfreeze_time(
+    mut paused_by_menu: ResMut<PausedByMenu>,
+    mut time: ResMut<Time<Virtual>>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    if paused_by_menu.time || paused_by_menu.physics {
+        debug!("resuming game");
+    }
+
+    if paused_by_menu.time {
+        time.unpause();
+    }
+    if paused_by_menu.physics {
+        physics_time.unpause();
+    }
+    *paused_by_menu = PausedByMenu::default();
+}
+
+fn spawn_pause_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            menu_screen("Pause Menu", "Paused", Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            StateScoped(GameState::Paused),
+        ))
+        .with_children(|menu| {
+            menu.spawn(menu_button("Resume", PauseMenuButton::Resume));
+            menu.spawn(menu_button("Settings", PauseMenuButton::Settings));
+            menu.spawn(menu_button("Main menu", PauseMenuButton::MainMenu));
+            menu.spawn(menu_button("Quit", PauseMenuButton::Quit));
+        });
+}
+
+fn handle_pause_menu_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &PauseMenuButton), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            PauseMenuButton::Resume => commands.trigger(RequestGameState(GameState::Playing)),
+            PauseMenuButton::Settings => commands.trigger(RequestGameState(GameState::Settings)),
+            PauseMenuButton::MainMenu => {
+                info!("returning to main menu");
+                next_state.set(AppState::MainMenu);
+            }
+            PauseMenuButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PlatformPath>()
+        .register_type::<PathMode>();
+
+    app.add_systems(FixedUpdate, move_platforms_along_path);
+}
+
+/// Moves a kinematic platform between waypoints by driving its velocity, so anything standing on
+/// it can read the velocity back and ride along.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(RigidBody::Kinematic)]
+pub struct PlatformPath {
+    pub waypoints: Vec<Vec3>,
+    pub speed: f32,
+    pub mode: PathMode,
+    pub wait: Timer,
+    target: usize,
+    reversed: bool,
+}
+
+impl PlatformPath {
+    pub fn new(waypoints: Vec<Vec3>, speed: f32) -> Self {
+        Self {
+            waypoints,
+            speed,
+            mode: PathMode::default(),
+            wait: Timer::default(),
+            target: 0,
+            reversed: false,
+        }
+    }
+
+    pub fn with_mode(mut self, mode: PathMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_wait(mut self, seconds: f32) -> Self {
+        self.wait = Timer::from_seconds(seconds, TimerMode::Once);
+        self
+    }
+
+    fn advance(&mut self) -> bool {
+        let last = self.waypoints.len() - 1;
+
+        match self.mode {
+            PathMode::Loop => {
+                self.target = (self.target + 1) % self.waypoints.len();
+            }
+            PathMode::PingPong => {
+                if self.target == last {
+                    self.reversed = true;
+                } else if self.target == 0 {
+                    self.reversed = false;
+                }
+
+                self.target = match self.reversed {
+                    true => self.target - 1,
+                    false => self.target + 1,
+                };
+            }
+            PathMode::Once => {
+                if self.target == last {
+                    return false;
+                }
+                self.target += 1;
+            }
+        }
+
+        true
+    }
+}
+
+#[derive(Default, Reflect, Debug, Copy, Clone, PartialEq)]
+pub enum PathMode {
+    #[default]
+    Loop,
+    PingPong,
+    Once,
+}
+
+fn move_platforms_along_path(
+    time: Res<Time>,
+    mut platforms: Query<(&mut PlatformPath, &Position, &mut LinearVelocity)>,
+) {
+    for (mut path, position, mut velocity) in platforms.iter_mut() {
+        if path.waypoints.len() < 2 {
+            velocity.0 = Vec3::ZERO;
+            continue;
+        }
+
+        path.wait.tick(time.delta());
+        if !path.wait.finished() {
+            velocity.0 = Vec3::ZERO;
+            continue;
+        }
+
+        let offset = path.waypoints[path.target] - position.0;
+        let step = path.speed * time.delta_secs();
+
+        if offset.length() <= step {
+            velocity.0 = Vec3::ZERO;
+            if path.advance() {
+                path.wait.reset();
+            }
+            continue;
+        }
+
+        velocity.0 = offset.normalize() * path.speed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(mode: PathMode) -> PlatformPath {
+        PlatformPath::new(vec![Vec3::ZERO, Vec3::X, Vec3::Y], 1.0).with_mode(mode)
+    }
+
+    fn targets(path: &mut PlatformPath, steps: usize) -> Vec<Option<usize>> {
+        (0..steps)
+            .map(|_| path.advance().then_some(path.target))
+            .collect()
+    }
+
+    #[test]
+    fn loop_wraps_to_the_first_waypoint() {
+        let mut path = path(PathMode::Loop);
+        assert_eq!(targets(&mut path, 4), [Some(1), Some(2), Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn ping_pong_turns_at_both_ends() {
+        let mut path = path(PathMode::PingPong);
+        assert_eq!(
+            targets(&mut path, 6),
+            [Some(1), Some(2), Some(1), Some(0), Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn once_stops_at_the_last_waypoint() {
+        let mut path = path(PathMode::Once);
+        assert_eq!(targets(&mut path, 3), [Some(1), Some(2), None]);
+        assert_eq!(path.target, 2);
+    }
+
+    #[test]
+    fn two_waypoints_ping_pong() {
+        let mut path =
+            PlatformPath::new(vec![Vec3::ZERO, Vec3::X], 1.0).with_mode(PathMode::PingPong);
+        assert_eq!(targets(&mut path, 4), [Some(1), Some(0), Some(1), Some(0)]);
+    }
+}
@@ -1,6 +1,12 @@
 use crate::gameplay::input::Rotate;
 use crate::gameplay::player::Player;
-use crate::{CameraOrder, DEFAULT_RENDER_LAYER, VIEW_MODEL_RENDER_LAYER};
+use crate::gameplay::player::camera_effects::{CameraEffects, PlayerView};
+use crate::gameplay::player::controller::GroundVelocity;
+use crate::gameplay::player::noclip::{DetachedCamera, FlyMode};
+use crate::gameplay::player::third_person::{CameraMode, SpringArm};
+use crate::{AppState, CameraOrder, DEFAULT_RENDER_LAYER, VIEW_MODEL_RENDER_LAYER};
+use avian3d::prelude::{TranslationExtrapolation, TranslationInterpolation};
+use bevy::pbr::Atmosphere;
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
 use bevy_enhanced_input::events::Fired;
@@ -8,41 +14,103 @@ use std::f32::consts::FRAC_PI_2;
 use crate::gameplay::items::inventory::{Equipped, EquippedItem};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraSmoothing>()
+        .register_type::<ViewModelCamera>();
+
+    app.init_resource::<CameraSmoothing>();
+
     app.add_observer(move_camera_with_look)
         .add_observer(spawn_player_camera)
         .add_observer(swap_held_item);
 
-    app.add_systems(Update, sync_camera_to_player_transform);
+    app.add_systems(
+        Update,
+        (
+            rotate_camera_with_ground,
+            apply_camera_smoothing.run_if(
+                resource_changed::<CameraSmoothing>.or(any_match_filter::<Added<Player>>),
+            ),
+        ),
+    );
+
+    // The player body is eased between physics ticks before `Update`, following it here means the
+    // camera always sees the final transform for the frame regardless of the physics tick rate.
+    app.add_systems(
+        PostUpdate,
+        sync_camera_to_player_transform.before(TransformSystem::TransformPropagate),
+    );
+}
+
+/// How the player body is smoothed between physics ticks for the camera to follow.
+#[derive(Resource, Reflect, Default, Debug, Copy, Clone, PartialEq)]
+#[reflect(Resource)]
+pub enum CameraSmoothing {
+    #[default]
+    Interpolate,
+    Extrapolate,
+    Off,
+}
+
+fn apply_camera_smoothing(
+    mut commands: Commands,
+    smoothing: Res<CameraSmoothing>,
+    players: Query<Entity, With<Player>>,
+) {
+    for player in players.iter() {
+        let mut player = commands.entity(player);
+        player.remove::<(TranslationInterpolation, TranslationExtrapolation)>();
+
+        match *smoothing {
+            CameraSmoothing::Interpolate => {
+                player.insert(TranslationInterpolation);
+            }
+            CameraSmoothing::Extrapolate => {
+                player.insert(TranslationExtrapolation);
+            }
+            CameraSmoothing::Off => {}
+        }
+    }
 }
 
 #[derive(Default, Component, Reflect, Debug)]
 #[reflect(Component)]
-#[require(Transform, Visibility)]
+#[require(Transform, Visibility, CameraEffects, CameraMode, SpringArm)]
 pub struct PlayerCamera;
 
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ViewModelCamera;
+
 pub fn spawn_player_camera(_trigger: Trigger<OnAdd, PlayerCameraTarget>, mut commands: Commands) {
     commands
         .spawn((
             Name::new("Camera Player"),
+            StateScoped(AppState::Gameplay),
             PlayerCamera,
             Transform::default().looking_at(Vec3::X, Vec3::Y),
         ))
         .with_children(|parent| {
             parent.spawn((
                 Name::new("Camera World"),
+                PlayerView,
                 Camera3d::default(),
                 Camera {
                     order: CameraOrder::World.into(),
+                    hdr: true,
                     ..default()
                 },
+                Atmosphere::EARTH,
                 RenderLayers::layer(DEFAULT_RENDER_LAYER),
             ));
 
             parent.spawn((
                 Name::new("Camera ViewModel"),
+                PlayerView,
+                ViewModelCamera,
                 Camera3d::default(),
                 Camera {
                     order: CameraOrder::ViewModel.into(),
+                    hdr: true,
                     ..default()
                 },
                 RenderLayers::layer(VIEW_MODEL_RENDER_LAYER),
@@ -54,6 +122,7 @@ pub fn move_camera_with_look(
     trigger: Trigger<Fired<Rotate>>,
     mut camera_transform: Single<&mut Transform, With<PlayerCamera>>,
     mut player_transform: Single<&mut Transform, (With<Player>, Without<PlayerCamera>)>,
+    fly_mode: Res<FlyMode>,
 ) {
     let delta = trigger.value;
     if delta != Vec2::ZERO {
@@ -67,7 +136,25 @@ pub fn move_camera_with_look(
         let pitch = (pitch + delta_pitch).clamp(-PITCH_LIMIT, PITCH_LIMIT);
 
         camera_transform.rotation = Quat::from_euler(EulerRot::YXZ, yaw, pitch, roll);
-        player_transform.rotation = Quat::from_rotation_y(yaw);
+        // The spectator camera looks around on its own, the body stays facing where it was left.
+        if *fly_mode != FlyMode::Spectator {
+            player_transform.rotation = Quat::from_rotation_y(yaw);
+        }
+    }
+}
+
+fn rotate_camera_with_ground(
+    time: Res<Time>,
+    mut camera_transform: Single<&mut Transform, With<PlayerCamera>>,
+    player: Single<(&mut Transform, &GroundVelocity), (With<Player>, Without<PlayerCamera>)>,
+) {
+    let (mut player_transform, ground_velocity) = player.into_inner();
+
+    let delta_yaw = ground_velocity.angular.y * time.delta_secs();
+    if delta_yaw != 0.0 {
+        let rotation = Quat::from_rotation_y(delta_yaw);
+        camera_transform.rotation = rotation * camera_transform.rotation;
+        player_transform.rotation = rotation * player_transform.rotation;
     }
 }
 
@@ -76,6 +163,7 @@ pub fn sync_camera_to_player_transform(
         &mut Transform,
         (
             With<PlayerCamera>,
+            Without<DetachedCamera>,
             Without<Player>,
             Without<PlayerCameraTarget>,
         ),
@@ -0,0 +1,235 @@
+use crate::gameplay::player::Player;
+use crate::gameplay::player::camera::PlayerCamera;
+use crate::gameplay::player::controller::{GroundVelocity, Grounded, Stance};
+use crate::gameplay::player::noclip::Flying;
+use crate::gameplay::settings::config::GameSettings;
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraEffects>()
+        .register_type::<CameraEffectsSettings>()
+        .register_type::<PlayerView>();
+
+    app.init_resource::<CameraEffectsSettings>();
+
+    app.add_observer(add_camera_trauma);
+
+    app.add_systems(
+        Update,
+        (
+            apply_effect_toggles.run_if(resource_changed::<GameSettings>),
+            (decay_trauma, update_headbob, update_landing_dip),
+            (apply_camera_offset, apply_fov_kick),
+        )
+            .chain(),
+    );
+}
+
+/// Toggles and tuning for each camera effect, all of them are composed on top of the look
+/// rotation so any of them can be switched off without affecting the others. The toggles follow
+/// the player's [`GameSettings`].
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct CameraEffectsSettings {
+    pub shake: bool,
+    pub headbob: bool,
+    pub landing_dip: bool,
+    pub fov_kick: bool,
+    pub max_shake_offset: f32,
+    pub max_shake_angle: f32,
+    pub shake_frequency: f32,
+    pub trauma_decay: f32,
+    pub bob_amplitude: f32,
+    pub bob_frequency: f32,
+    pub bob_reference_speed: f32,
+    pub dip_per_fall_speed: f32,
+    pub max_dip: f32,
+    pub dip_recovery: f32,
+    pub sprint_fov_kick: f32,
+    pub fov_smoothing: f32,
+}
+
+impl Default for CameraEffectsSettings {
+    fn default() -> Self {
+        Self {
+            shake: true,
+            headbob: true,
+            landing_dip: true,
+            fov_kick: true,
+            max_shake_offset: 0.15,
+            max_shake_angle: 0.05,
+            shake_frequency: 25.0,
+            trauma_decay: 1.5,
+            bob_amplitude: 0.04,
+            bob_frequency: 1.8,
+            bob_reference_speed: 5.0,
+            dip_per_fall_speed: 0.015,
+            max_dip: 0.3,
+            dip_recovery: 8.0,
+            sprint_fov_kick: 8.0,
+            fov_smoothing: 8.0,
+        }
+    }
+}
+
+fn apply_effect_toggles(
+    game_settings: Res<GameSettings>,
+    mut settings: ResMut<CameraEffectsSettings>,
+) {
+    let gameplay = &game_settings.gameplay;
+    settings.shake = gameplay.camera_shake;
+    settings.headbob = gameplay.headbob;
+    settings.landing_dip = gameplay.landing_dip;
+    settings.fov_kick = gameplay.fov_kick;
+}
+
+/// Per camera effect state, lives on the [`PlayerCamera`].
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CameraEffects {
+    pub trauma: f32,
+    bob_phase: f32,
+    bob_offset: Vec3,
+    dip: f32,
+    fall_speed: f32,
+    was_grounded: bool,
+}
+
+/// Marks the cameras under the [`PlayerCamera`] that the effects offset is written to.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct PlayerView;
+
+/// Adds trauma to the player camera, shake strength is the square of the accumulated trauma.
+#[derive(Event, Debug)]
+pub struct CameraShake(pub f32);
+
+fn add_camera_trauma(trigger: Trigger<CameraShake>, mut effects: Single<&mut CameraEffects>) {
+    effects.trauma = (effects.trauma + trigger.0).clamp(0.0, 1.0);
+}
+
+fn decay_trauma(
+    time: Res<Time>,
+    settings: Res<CameraEffectsSettings>,
+    mut effects: Single<&mut CameraEffects>,
+) {
+    effects.trauma = (effects.trauma - settings.trauma_decay * time.delta_secs()).max(0.0);
+}
+
+fn update_headbob(
+    time: Res<Time>,
+    settings: Res<CameraEffectsSettings>,
+    mut effects: Single<&mut CameraEffects>,
+    player: Single<(&LinearVelocity, &GroundVelocity, Has<Grounded>), With<Player>>,
+) {
+    let (velocity, ground_velocity, is_grounded) = player.into_inner();
+
+    let relative = velocity.0 - ground_velocity.linear;
+    let ground_speed = Vec2::new(relative.x, relative.z).length();
+
+    let target = if settings.headbob && is_grounded && ground_speed > 0.1 {
+        effects.bob_phase += ground_speed * settings.bob_frequency * time.delta_secs();
+        let weight = (ground_speed / settings.bob_reference_speed).clamp(0.0, 1.0);
+        Vec3::new(
+            effects.bob_phase.cos() * settings.bob_amplitude * 0.5,
+            (effects.bob_phase * 2.0).sin().abs() * settings.bob_amplitude,
+            0.0,
+        ) * weight
+    } else {
+        Vec3::ZERO
+    };
+
+    let blend = 1.0 - (-10.0 * time.delta_secs()).exp();
+    effects.bob_offset = effects.bob_offset.lerp(target, blend);
+}
+
+fn update_landing_dip(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<CameraEffectsSettings>,
+    mut effects: Single<&mut CameraEffects>,
+    player: Single<(&LinearVelocity, Has<Grounded>), With<Player>>,
+) {
+    let (velocity, is_grounded) = player.into_inner();
+
+    if !is_grounded {
+        effects.fall_speed = (-velocity.y).max(0.0);
+    } else if !effects.was_grounded {
+        if settings.landing_dip {
+            effects.dip = -(effects.fall_speed * settings.dip_per_fall_speed).min(settings.max_dip);
+        }
+
+        let impact = effects.fall_speed / 40.0;
+        if impact > 0.2 {
+            commands.trigger(CameraShake(impact));
+        }
+        effects.fall_speed = 0.0;
+    }
+    effects.was_grounded = is_grounded;
+
+    let blend = 1.0 - (-settings.dip_recovery * time.delta_secs()).exp();
+    effects.dip = effects.dip.lerp(0.0, blend);
+}
+
+fn apply_camera_offset(
+    time: Res<Time>,
+    settings: Res<CameraEffectsSettings>,
+    effects: Single<&CameraEffects, With<PlayerCamera>>,
+    mut views: Query<&mut Transform, With<PlayerView>>,
+) {
+    let mut translation = effects.bob_offset + Vec3::Y * effects.dip;
+    let mut rotation = Quat::IDENTITY;
+
+    if settings.shake && effects.trauma > 0.0 {
+        let shake = effects.trauma * effects.trauma;
+        let t = time.elapsed_secs() * settings.shake_frequency;
+
+        translation += Vec3::new(wobble(t, 0.0), wobble(t, 1.3), wobble(t, 2.7))
+            * settings.max_shake_offset
+            * shake;
+        rotation = Quat::from_euler(
+            EulerRot::YXZ,
+            wobble(t, 4.1) * settings.max_shake_angle * shake,
+            wobble(t, 5.9) * settings.max_shake_angle * shake,
+            wobble(t, 7.3) * settings.max_shake_angle * shake,
+        );
+    }
+
+    for mut transform in views.iter_mut() {
+        transform.translation = translation;
+        transform.rotation = rotation;
+    }
+}
+
+fn apply_fov_kick(
+    time: Res<Time>,
+    settings: Res<CameraEffectsSettings>,
+    game_settings: Res<GameSettings>,
+    player: Single<(&LinearVelocity, &GroundVelocity, &Stance, Has<Flying>), With<Player>>,
+    mut views: Query<&mut Projection, With<PlayerView>>,
+) {
+    let (velocity, ground_velocity, stance, is_flying) = player.into_inner();
+
+    // Relative to the ground, so riding a moving platform doesn't read as sprinting. Sprint is
+    // the fast fly key while flying, which shouldn't kick the FOV either.
+    let speed = (velocity.0 - ground_velocity.linear).xz().length();
+    let is_sprinting = *stance == Stance::Sprinting && speed > 1.0 && !is_flying;
+    let target = match settings.fov_kick && is_sprinting {
+        true => game_settings.video.fov + settings.sprint_fov_kick,
+        false => game_settings.video.fov,
+    }
+    .to_radians();
+
+    let blend = 1.0 - (-settings.fov_smoothing * time.delta_secs()).exp();
+    for mut projection in views.iter_mut() {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = perspective.fov.lerp(target, blend);
+        }
+    }
+}
+
+/// Cheap smooth noise in `-1..=1` made from two incommensurate sine waves.
+fn wobble(t: f32, seed: f32) -> f32 {
+    ((t + seed * 17.0).sin() + (t * 1.618 + seed * 31.0).sin()) * 0.5
+}
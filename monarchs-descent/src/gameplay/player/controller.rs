@@ -1,6 +1,7 @@
-use crate::gameplay::input::{Crouch, Jump, Move, PlayerActions};
+use crate::gameplay::input::{Crouch, Jump, Move, PlayerActions, Sprint};
 use crate::gameplay::player::Player;
 use crate::gameplay::player::inventory::Holding;
+use crate::gameplay::player::noclip::Flying;
 use avian3d::prelude::*;
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
@@ -11,7 +12,8 @@ pub(super) fn plugin(app: &mut App) {
         .register_type::<MovementDamping>()
         .register_type::<MovementAcceleration>()
         .register_type::<CrouchModifier>()
-        .register_type::<JumpImpulse>();
+        .register_type::<JumpImpulse>()
+        .register_type::<GroundVelocity>();
 
     app.add_observer(apply_player_movement)
         .add_observer(apply_player_jump);
@@ -37,6 +39,7 @@ pub(super) struct PlayerControllerBundle {
     movement_damping: MovementDamping,
     jump_impulse: JumpImpulse,
     crouch_modifier: CrouchModifier,
+    ground_velocity: GroundVelocity,
 }
 
 impl PlayerControllerBundle {
@@ -55,6 +58,7 @@ impl PlayerControllerBundle {
             movement_damping: MovementDamping::new(0.3, 0.98),
             jump_impulse: JumpImpulse(5.0),
             crouch_modifier: CrouchModifier(0.3),
+            ground_velocity: GroundVelocity::default(),
         }
     }
 }
@@ -70,6 +74,7 @@ pub enum Stance {
     #[default]
     Standing,
     Crouching,
+    Sprinting,
 }
 
 #[derive(Default, Component, Reflect, Debug, InspectorOptions)]
@@ -106,30 +111,77 @@ impl MovementDamping {
 #[reflect(Component, InspectorOptions)]
 pub struct CrouchModifier(pub f32);
 
-fn check_grounded(mut commands: Commands, player: Single<(Entity, &ShapeHits), With<Player>>) {
-    let (entity, hits) = player.into_inner();
+/// Velocity of the body the player is standing on, sampled at the player's position.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct GroundVelocity {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
 
-    let is_grounded = hits.iter().any(|_hit| return true);
+fn check_grounded(
+    mut commands: Commands,
+    player: Single<(Entity, &ShapeHits, &Position, &mut GroundVelocity), With<Player>>,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<
+        (
+            &LinearVelocity,
+            &AngularVelocity,
+            &Position,
+            &Rotation,
+            &ComputedCenterOfMass,
+        ),
+        Without<Player>,
+    >,
+) {
+    let (entity, hits, position, mut ground_velocity) = player.into_inner();
 
-    if is_grounded {
-        commands.entity(entity).insert(Grounded);
-    } else {
+    let Some(hit) = hits.iter().next() else {
         commands.entity(entity).remove::<Grounded>();
-    }
+        *ground_velocity = GroundVelocity::default();
+        return;
+    };
+
+    commands.entity(entity).insert(Grounded);
+
+    let body = colliders
+        .get(hit.entity)
+        .map_or(hit.entity, |collider_of| collider_of.body);
+
+    *ground_velocity = match bodies.get(body) {
+        Ok((linear, angular, body_position, body_rotation, center_of_mass)) => {
+            let center = body_position.0 + body_rotation.0 * center_of_mass.0;
+            GroundVelocity {
+                linear: linear.0 + angular.0.cross(position.0 - center),
+                angular: angular.0,
+            }
+        }
+        Err(_) => GroundVelocity::default(),
+    };
 }
 
 fn apply_player_movement_damping(
-    player: Single<(&mut LinearVelocity, &MovementDamping, Has<Grounded>), With<Player>>,
+    player: Single<
+        (
+            &mut LinearVelocity,
+            &MovementDamping,
+            &GroundVelocity,
+            Has<Grounded>,
+        ),
+        (With<Player>, Without<Flying>),
+    >,
 ) {
-    let (mut velocity, movement_damping, is_grounded) = player.into_inner();
+    let (mut velocity, movement_damping, ground_velocity, is_grounded) = player.into_inner();
 
-    if is_grounded {
-        velocity.x *= movement_damping.ground;
-        velocity.z *= movement_damping.ground;
-    } else {
-        velocity.x *= movement_damping.air;
-        velocity.z *= movement_damping.air;
-    }
+    let damping = match is_grounded {
+        true => movement_damping.ground,
+        false => movement_damping.air,
+    };
+
+    // Damp relative to whatever we are standing on so moving platforms carry the player.
+    let relative = velocity.0 - ground_velocity.linear;
+    velocity.x = ground_velocity.linear.x + relative.x * damping;
+    velocity.z = ground_velocity.linear.z + relative.z * damping;
 }
 
 fn apply_player_movement(
@@ -142,7 +194,7 @@ fn apply_player_movement(
             &Stance,
             Has<Grounded>,
         ),
-        With<Player>,
+        (With<Player>, Without<Flying>),
     >,
 ) {
     let (mut velocity, movement_acceleration, rotation, stance, is_grounded) = player.into_inner();
@@ -151,6 +203,7 @@ fn apply_player_movement(
     let movement_modifier = match stance {
         Stance::Standing => 1.0,
         Stance::Crouching => 0.5,
+        Stance::Sprinting => 1.6,
     };
 
     if is_grounded {
@@ -164,7 +217,10 @@ fn apply_player_movement(
 
 fn apply_player_jump(
     trigger: Trigger<Fired<Jump>>,
-    player: Single<(&mut LinearVelocity, &JumpImpulse, Has<Grounded>), With<Player>>,
+    player: Single<
+        (&mut LinearVelocity, &JumpImpulse, Has<Grounded>),
+        (With<Player>, Without<Flying>),
+    >,
 ) {
     let (mut velocity, jump_impulse, is_grounded) = player.into_inner();
     let jump = trigger.value;
@@ -178,7 +234,7 @@ fn apply_player_jump(
 
 fn apply_player_stance(
     actions: Single<&Actions<PlayerActions>>,
-    player: Single<&mut Stance, With<Player>>,
+    player: Single<&mut Stance, (With<Player>, Without<Flying>)>,
 ) {
     let mut stance = player.into_inner();
     let actions = actions.into_inner();
@@ -186,6 +242,8 @@ fn apply_player_stance(
 
     if actions.action::<Crouch>().state() == ActionState::Fired {
         *stance = Stance::Crouching;
+    } else if actions.action::<Sprint>().state() == ActionState::Fired {
+        *stance = Stance::Sprinting;
     } else {
         *stance = Stance::Standing;
     }
@@ -194,6 +252,7 @@ fn apply_player_stance(
         match *stance {
             Stance::Crouching => {}
             Stance::Standing => {}
+            Stance::Sprinting => {}
         }
     }
 }
@@ -1,51 +1,124 @@
 use crate::GameState;
+use crate::gameplay::settings::config::GameSettings;
+use bevy::ecs::spawn::SpawnIter;
 use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+const PROGRESS_SEGMENTS: usize = 24;
+const PROGRESS_RADIUS: f32 = 22.0;
+const PROGRESS_DOT_SIZE: f32 = 5.0;
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<CrosshairResource>();
+    app.register_type::<CrosshairProgress>();
 
-    app.init_resource::<CrosshairResource>();
+    app.init_resource::<CrosshairProgress>();
 
     app.add_systems(OnEnter(GameState::Playing), show_crosshair);
-}
 
-#[derive(Resource, Reflect, Debug)]
-#[reflect(Resource)]
-struct CrosshairResource {
-    size: f32,
+    app.add_systems(
+        Update,
+        (
+            update_progress_ring.run_if(resource_changed::<CrosshairProgress>),
+            resize_crosshair.run_if(resource_changed::<GameSettings>),
+        ),
+    );
 }
 
-impl Default for CrosshairResource {
-    fn default() -> Self {
-        Self { size: 2.0 }
-    }
-}
+/// Fraction of the radial progress ring drawn around the crosshair, hidden when `None`.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub(super) struct CrosshairProgress(pub Option<f32>);
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 struct Crosshair;
 
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct CrosshairImage;
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct ProgressSegment(usize);
+
 fn show_crosshair(
     mut commands: Commands,
     asset_server: ResMut<AssetServer>,
-    crosshair: Res<CrosshairResource>,
+    settings: Res<GameSettings>,
 ) {
-    commands.spawn((
-        Crosshair,
-        StateScoped(GameState::Playing),
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            ..default()
-        },
-        children![(
+    commands
+        .spawn((
+            Crosshair,
+            StateScoped(GameState::Playing),
             Node {
-                width: Val::Percent(crosshair.size),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
                 ..default()
             },
-            ImageNode::new(asset_server.load("Crosshair.png")),
-        )],
-    ));
+            children![(
+                CrosshairImage,
+                Node {
+                    width: Val::Percent(settings.gameplay.crosshair_size),
+                    ..default()
+                },
+                ImageNode::new(asset_server.load("Crosshair.png")),
+            )],
+        ))
+        .with_child((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(PROGRESS_RADIUS * 2.0),
+                height: Val::Px(PROGRESS_RADIUS * 2.0),
+                ..default()
+            },
+            Children::spawn(SpawnIter((0..PROGRESS_SEGMENTS).map(progress_segment))),
+        ));
+}
+
+fn resize_crosshair(
+    settings: Res<GameSettings>,
+    mut images: Query<&mut Node, With<CrosshairImage>>,
+) {
+    for mut node in images.iter_mut() {
+        node.width = Val::Percent(settings.gameplay.crosshair_size);
+    }
+}
+
+fn progress_segment(index: usize) -> impl Bundle {
+    // Start at the top and go clockwise.
+    let angle = index as f32 / PROGRESS_SEGMENTS as f32 * TAU - TAU / 4.0;
+    let offset = PROGRESS_RADIUS - PROGRESS_DOT_SIZE / 2.0;
+
+    (
+        ProgressSegment(index),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(offset + angle.cos() * offset),
+            top: Val::Px(offset + angle.sin() * offset),
+            width: Val::Px(PROGRESS_DOT_SIZE),
+            height: Val::Px(PROGRESS_DOT_SIZE),
+            ..default()
+        },
+        BorderRadius::MAX,
+        BackgroundColor(Color::WHITE),
+        Visibility::Hidden,
+    )
+}
+
+fn update_progress_ring(
+    progress: Res<CrosshairProgress>,
+    mut segments: Query<(&ProgressSegment, &mut Visibility)>,
+) {
+    let filled = progress.0.map_or(0, |progress| {
+        (progress.clamp(0.0, 1.0) * PROGRESS_SEGMENTS as f32) as usize
+    });
+
+    for (segment, mut visibility) in segments.iter_mut() {
+        *visibility = match segment.0 < filled {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
 }
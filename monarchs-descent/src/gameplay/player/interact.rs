@@ -1,32 +1,347 @@
-use crate::gameplay::input::Interact;
-use crate::gameplay::items::inventory::{Inventory};
+use crate::GameState;
+use crate::gameplay::highlight::Highlighted;
+use crate::gameplay::input::{HoldInteract, Interact, button_label, key_label};
+use crate::gameplay::interactable::{Interactable, Interacted};
 use crate::gameplay::items::Item;
+use crate::gameplay::items::pickup::in_loot_range;
 use crate::gameplay::player::Player;
+use crate::gameplay::player::camera::PlayerCamera;
+use crate::gameplay::player::crosshair::CrosshairProgress;
+use crate::gameplay::settings::bindings::InputBindings;
+use avian3d::prelude::*;
 use bevy::prelude::*;
-use bevy_enhanced_input::prelude::Started;
+use bevy_enhanced_input::prelude::{Canceled, Completed, Fired, Ongoing, Started};
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<InteractionRange>();
+    app.register_type::<InteractionRange>()
+        .register_type::<InteractionTarget>()
+        .register_type::<InteractionPrompt>()
+        .register_type::<InteractionConsumed>()
+        .register_type::<HoldProgress>();
 
-    app.add_observer(simple_pickup);
+    app.add_observer(start_interaction)
+        .add_observer(hold_ongoing)
+        .add_observer(hold_fired)
+        .add_observer(hold_completed)
+        .add_observer(hold_canceled);
+
+    app.add_systems(OnEnter(GameState::Playing), spawn_interaction_prompt);
+
+    app.add_systems(
+        Update,
+        (
+            update_interaction_target,
+            (cancel_hold_on_focus_change, update_interaction_prompt),
+        )
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
 }
 
 #[derive(Component, Reflect, Debug)]
 #[reflect(Component)]
 pub(super) struct InteractionRange(pub f32);
 
-fn simple_pickup(
-    _trigger: Trigger<Started<Interact>>,
-    player: Single<(Entity, &Transform, &InteractionRange, &mut Inventory), With<Player>>,
-    items: Query<(Entity, &Transform, &Item)>,
+/// The [`Interactable`] currently under the crosshair.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+pub(super) struct InteractionTarget(pub Option<Entity>);
+
+/// Set once the held `Interact` press has been used up, so holding doesn't repeat the interaction.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+struct InteractionConsumed;
+
+/// A held `Interact` press working towards an [`Interactable`] with a hold duration.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+struct HoldProgress {
+    target: Entity,
+    /// Seconds the press had been held for when `target` was focused, the hold counts from here.
+    started: f32,
+}
+
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct InteractionPrompt;
+
+fn update_interaction_target(
     mut commands: Commands,
+    spatial_query: SpatialQuery,
+    camera: Single<&GlobalTransform, With<PlayerCamera>>,
+    player: Single<
+        (
+            Entity,
+            &GlobalTransform,
+            &InteractionRange,
+            &mut InteractionTarget,
+        ),
+        With<Player>,
+    >,
+    colliders: Query<&ColliderOf>,
+    interactables: Query<&Interactable>,
+    loot: Query<&GlobalTransform, With<Item>>,
 ) {
-    let (player, player_transform, range, mut inventory) = player.into_inner();
-    items.iter().for_each(|(item, transform, item_state)| {
-        if (transform.translation - player_transform.translation).length() < range.0 {
-            if inventory.insert_item(item).is_err() {
-                return;
-            };
+    let (player, player_transform, range, mut target) = player.into_inner();
+
+    let origin = camera.translation();
+    let direction = camera.forward();
+    // The camera can sit behind the player, so reach past it but measure range from the player.
+    let max_distance = range.0 + origin.distance(player_transform.translation());
+    let filter = SpatialQueryFilter::from_excluded_entities([player]);
+
+    let hit = spatial_query
+        .cast_ray(origin, direction, max_distance, true, &filter)
+        .map(|hit| (hit.entity, hit.distance))
+        .or_else(|| {
+            spatial_query
+                .cast_shape(
+                    &Collider::sphere(0.1),
+                    origin,
+                    Quat::IDENTITY,
+                    direction,
+                    &ShapeCastConfig::from_max_distance(max_distance),
+                    &filter,
+                )
+                .map(|hit| (hit.entity, hit.distance))
+        });
+
+    let focused = hit.and_then(|(entity, distance)| {
+        let point = origin + direction * distance;
+        if point.distance(player_transform.translation()) > range.0 {
+            return None;
         }
-    })
+
+        let body = colliders
+            .get(entity)
+            .map_or(entity, |collider_of| collider_of.body);
+
+        [entity, body].into_iter().find(|&candidate| {
+            interactables
+                .get(candidate)
+                .is_ok_and(|interactable| interactable.enabled)
+        })
+    });
+
+    if focused != target.0 {
+        // Loot still in range goes back to its loot outline instead of flickering off for a frame.
+        if let Some(previous) = target.0 {
+            match loot.get(previous) {
+                Ok(transform)
+                    if in_loot_range(transform.translation(), player_transform.translation()) =>
+                {
+                    commands.entity(previous).try_insert(Highlighted::Loot);
+                }
+                _ => {
+                    commands.entity(previous).try_remove::<Highlighted>();
+                }
+            }
+        }
+        if let Some(next) = focused {
+            commands.entity(next).insert(Highlighted::Focus);
+        }
+
+        target.0 = focused;
+    }
+}
+
+fn spawn_interaction_prompt(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Interaction Prompt"),
+        StateScoped(GameState::Playing),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            padding: UiRect::top(Val::Px(120.0)),
+            ..default()
+        },
+        children![(
+            InteractionPrompt,
+            Text::default(),
+            TextFont::from_font_size(20.0),
+            Visibility::Hidden,
+        )],
+    ));
+}
+
+fn update_interaction_prompt(
+    target: Single<&InteractionTarget, With<Player>>,
+    interactables: Query<(&Interactable, Option<&Name>)>,
+    prompt: Single<(&mut Text, &mut Visibility), With<InteractionPrompt>>,
+    bindings: InputBindings,
+) {
+    let (mut text, mut visibility) = prompt.into_inner();
+
+    let Some((interactable, name)) = target.0.and_then(|entity| interactables.get(entity).ok())
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let action = match interactable.hold {
+        Some(_) => "Hold",
+        None => "Press",
+    };
+    let binding = bindings.get::<Interact>();
+    let input = binding
+        .keyboard
+        .map(key_label)
+        .or_else(|| binding.gamepad.map(button_label))
+        .unwrap_or_else(|| "Interact".to_string());
+
+    text.0 = format!(
+        "{action} {input} to {} {}",
+        interactable.verb,
+        interactable.label(name),
+    );
+    *visibility = Visibility::Inherited;
+}
+
+fn start_interaction(
+    trigger: Trigger<Started<Interact>>,
+    mut commands: Commands,
+    player: Single<(Entity, &InteractionTarget), With<Player>>,
+    interactables: Query<&Interactable>,
+) {
+    let (actor, target) = player.into_inner();
+    commands.entity(actor).remove::<InteractionConsumed>();
+
+    let Some(target) = target.0 else {
+        return;
+    };
+    let Ok(interactable) = interactables.get(target) else {
+        return;
+    };
+
+    if interactable.hold.is_none() && interactable.is_ready() {
+        debug!(?actor, ?target, ?trigger, "interacting");
+        commands.trigger_targets(Interacted { actor, target }, target);
+        commands.entity(actor).insert(InteractionConsumed);
+    }
+}
+
+fn hold_ongoing(
+    trigger: Trigger<Ongoing<HoldInteract>>,
+    mut commands: Commands,
+    player: Single<
+        (Entity, &InteractionTarget, Option<&HoldProgress>),
+        (With<Player>, Without<InteractionConsumed>),
+    >,
+    interactables: Query<&Interactable>,
+    mut progress: ResMut<CrosshairProgress>,
+) {
+    advance_hold(
+        &mut commands,
+        trigger.elapsed_secs,
+        player.into_inner(),
+        &interactables,
+        &mut progress,
+    );
+}
+
+fn hold_fired(
+    trigger: Trigger<Fired<HoldInteract>>,
+    mut commands: Commands,
+    player: Single<
+        (Entity, &InteractionTarget, Option<&HoldProgress>),
+        (With<Player>, Without<InteractionConsumed>),
+    >,
+    interactables: Query<&Interactable>,
+    mut progress: ResMut<CrosshairProgress>,
+) {
+    advance_hold(
+        &mut commands,
+        trigger.elapsed_secs,
+        player.into_inner(),
+        &interactables,
+        &mut progress,
+    );
+}
+
+fn advance_hold(
+    commands: &mut Commands,
+    elapsed: f32,
+    (actor, target, hold_progress): (Entity, &InteractionTarget, Option<&HoldProgress>),
+    interactables: &Query<&Interactable>,
+    progress: &mut CrosshairProgress,
+) {
+    let Some(target) = target.0 else {
+        return;
+    };
+    let Ok(interactable) = interactables.get(target) else {
+        return;
+    };
+    let Some(hold) = interactable.hold else {
+        return;
+    };
+
+    if !interactable.is_ready() {
+        return;
+    }
+    // The press may have started while looking at something else, only time on target counts.
+    let started = match hold_progress {
+        Some(hold_progress) if hold_progress.target != target => return,
+        Some(hold_progress) => hold_progress.started,
+        None => elapsed,
+    };
+    let held = elapsed - started;
+
+    if held >= hold {
+        debug!(?actor, ?target, "interacting after hold");
+        commands.trigger_targets(Interacted { actor, target }, target);
+        commands
+            .entity(actor)
+            .remove::<HoldProgress>()
+            .insert(InteractionConsumed);
+        progress.0 = None;
+    } else {
+        if hold_progress.is_none() {
+            commands
+                .entity(actor)
+                .insert(HoldProgress { target, started });
+        }
+        progress.0 = Some(held / hold);
+    }
+}
+
+fn hold_completed(
+    _trigger: Trigger<Completed<HoldInteract>>,
+    mut commands: Commands,
+    player: Single<Entity, With<Player>>,
+    mut progress: ResMut<CrosshairProgress>,
+) {
+    commands.entity(*player).remove::<HoldProgress>();
+    progress.0 = None;
+}
+
+fn hold_canceled(
+    _trigger: Trigger<Canceled<HoldInteract>>,
+    mut commands: Commands,
+    player: Single<Entity, With<Player>>,
+    mut progress: ResMut<CrosshairProgress>,
+) {
+    commands.entity(*player).remove::<HoldProgress>();
+    progress.0 = None;
+}
+
+/// Looking away or moving out of range drops the hold, it only restarts on a fresh press.
+fn cancel_hold_on_focus_change(
+    mut commands: Commands,
+    player: Single<(Entity, &InteractionTarget, &HoldProgress), With<Player>>,
+    mut progress: ResMut<CrosshairProgress>,
+) {
+    let (actor, target, hold_progress) = player.into_inner();
+
+    if target.0 != Some(hold_progress.target) {
+        debug!(?actor, "hold interaction canceled");
+        commands
+            .entity(actor)
+            .remove::<HoldProgress>()
+            .insert(InteractionConsumed);
+        progress.0 = None;
+    }
 }
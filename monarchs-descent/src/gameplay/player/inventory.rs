@@ -1,75 +1,299 @@
 use crate::GameState;
-use crate::gameplay::input::{CloseInventory, InventoryActions, OpenInventory, PlayerActions};
+use crate::gameplay::input::{CloseInventory, OpenInventory};
+use crate::gameplay::interactable::Interacted;
+use crate::gameplay::items::container::Container;
 use crate::gameplay::items::inventory::Inventory;
 use crate::gameplay::player::Player;
+use crate::gameplay::transition::{RequestGameState, can_transition};
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
+const SLOT_SIZE: f32 = 72.0;
+const SLOT_COLUMNS: u16 = 6;
+
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<Holding>();
+    app.register_type::<Holding>()
+        .register_type::<OpenContainer>();
+
+    app.init_resource::<OpenContainer>();
 
     app.add_observer(open_inventory)
-        .add_observer(close_inventory);
+        .add_observer(close_inventory)
+        .add_observer(open_container);
+
+    app.add_systems(OnEnter(GameState::Inventory), spawn_inventory_screen)
+        .add_systems(OnExit(GameState::Inventory), clear_open_container);
 
-    app.add_systems(OnEnter(GameState::Inventory), show_inventory);
+    app.add_systems(
+        Update,
+        (handle_inventory_buttons, refresh_inventory_screen)
+            .chain()
+            .run_if(in_state(GameState::Inventory)),
+    );
 }
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub(super) struct Holding(pub Entity);
 
-fn open_inventory(
-    trigger: Trigger<Started<OpenInventory>>,
+/// Container shown next to the player's inventory while in [`GameState::Inventory`].
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub(super) struct OpenContainer(pub Option<Entity>);
+
+#[derive(Component, Debug)]
+struct InventoryScreen;
+
+/// Clicking moves the item in `slot` of `owner` over to the other inventory.
+#[derive(Component, Debug)]
+struct SlotButton {
+    owner: Entity,
+    slot: usize,
+}
+
+#[derive(Component, Debug)]
+enum InventoryButton {
+    TakeAll,
+    Sort(Entity),
+}
+
+fn open_inventory(_trigger: Trigger<Started<OpenInventory>>, mut commands: Commands) {
+    commands.trigger(RequestGameState(GameState::Inventory));
+}
+
+fn close_inventory(_trigger: Trigger<Started<CloseInventory>>, mut commands: Commands) {
+    commands.trigger(RequestGameState(GameState::Playing));
+}
+
+fn open_container(
+    trigger: Trigger<Interacted>,
     mut commands: Commands,
+    containers: Query<(), (With<Container>, With<Inventory>)>,
     curr_state: Res<State<GameState>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    mut open_container: ResMut<OpenContainer>,
 ) {
-    if *curr_state != GameState::Playing {
-        error!(
+    let container = trigger.target();
+    if !containers.contains(container) {
+        return;
+    }
+
+    // Checked up front so a rejected transition doesn't leave a stale container behind.
+    if !can_transition(*curr_state.get(), GameState::Inventory) {
+        warn!(
             ?curr_state,
-            ?trigger,
-            "Entering inventory from an incorrect state"
+            ?container,
+            "can't open container from this state"
         );
-        panic!("Entering inventory from an incorrect state");
+        return;
     }
 
-    debug!("opening inventory");
+    debug!(?container, "opening container");
 
-    commands
-        .entity(trigger.target())
-        .remove::<Actions<PlayerActions>>()
-        .insert(Actions::<InventoryActions>::default());
+    open_container.0 = Some(container);
+    commands.trigger(RequestGameState(GameState::Inventory));
+}
+
+fn clear_open_container(mut open_container: ResMut<OpenContainer>) {
+    open_container.0 = None;
+}
 
-    next_state.set(GameState::Inventory);
+fn spawn_inventory_screen(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Inventory Screen"),
+        InventoryScreen,
+        StateScoped(GameState::Inventory),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(48.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+    ));
 }
 
-fn close_inventory(
-    trigger: Trigger<Started<CloseInventory>>,
+fn refresh_inventory_screen(
     mut commands: Commands,
-    curr_state: Res<State<GameState>>,
-    mut next_state: ResMut<NextState<GameState>>,
+    screen: Single<Entity, With<InventoryScreen>>,
+    player: Single<Entity, With<Player>>,
+    open_container: Res<OpenContainer>,
+    inventories: Query<&Inventory>,
+    changed_inventories: Query<(), Changed<Inventory>>,
+    new_screen: Query<(), Added<InventoryScreen>>,
+    names: Query<&Name>,
 ) {
-    if *curr_state != GameState::Inventory {
-        error!(
-            ?curr_state,
-            ?trigger,
-            "Closing inventory from an incorrect state"
-        );
-        panic!("Closing inventory from an incorrect state");
+    if changed_inventories.is_empty() && new_screen.is_empty() && !open_container.is_changed() {
+        return;
+    }
+
+    commands.entity(*screen).despawn_related::<Children>();
+
+    let panes = [Some(*player), open_container.0];
+    for owner in panes.into_iter().flatten() {
+        let Ok(inventory) = inventories.get(owner) else {
+            continue;
+        };
+
+        let title = names.get(owner).map_or("Container", |name| name.as_str());
+        let is_container = owner != *player;
+        let pane =
+            spawn_inventory_pane(&mut commands, owner, title, inventory, &names, is_container);
+        commands.entity(*screen).add_child(pane);
     }
+}
+
+fn spawn_inventory_pane(
+    commands: &mut Commands,
+    owner: Entity,
+    title: &str,
+    inventory: &Inventory,
+    names: &Query<&Name>,
+    is_container: bool,
+) -> Entity {
+    let pane = commands
+        .spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                padding: UiRect::all(Val::Px(12.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.9)),
+            children![(Text::new(title), TextFont::from_font_size(24.0))],
+        ))
+        .id();
+
+    let grid = commands
+        .spawn(Node {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::px(SLOT_COLUMNS, SLOT_SIZE),
+            row_gap: Val::Px(4.0),
+            column_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|grid| {
+            for (slot, item) in inventory.get_items().iter().enumerate() {
+                let label = item
+                    .and_then(|item| names.get(item).ok())
+                    .map_or(String::new(), |name| name.to_string());
 
-    debug!("Closing inventory");
+                grid.spawn((
+                    Button,
+                    SlotButton { owner, slot },
+                    Node {
+                        width: Val::Px(SLOT_SIZE),
+                        height: Val::Px(SLOT_SIZE),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.2, 0.24)),
+                    children![(Text::new(label), TextFont::from_font_size(12.0))],
+                ));
+            }
+        })
+        .id();
 
-    commands
-        .entity(trigger.target())
-        .remove::<Actions<InventoryActions>>()
-        .insert(Actions::<PlayerActions>::default());
+    let buttons = commands
+        .spawn(Node {
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|buttons| {
+            buttons.spawn(inventory_button("Sort", InventoryButton::Sort(owner)));
+            if is_container {
+                buttons.spawn(inventory_button("Take all", InventoryButton::TakeAll));
+            }
+        })
+        .id();
+
+    commands.entity(pane).add_children(&[grid, buttons]);
+    pane
+}
 
-    next_state.set(GameState::Playing);
+fn inventory_button(label: &str, button: InventoryButton) -> impl Bundle {
+    (
+        Button,
+        button,
+        Node {
+            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.3, 0.3, 0.36)),
+        children![(Text::new(label), TextFont::from_font_size(16.0))],
+    )
 }
 
-fn show_inventory(player_inventory: Single<&Inventory, With<Player>>) {
-    let inventory = player_inventory.into_inner();
+fn handle_inventory_buttons(
+    slots: Query<(&Interaction, &SlotButton), Changed<Interaction>>,
+    buttons: Query<(&Interaction, &InventoryButton), Changed<Interaction>>,
+    player: Single<Entity, With<Player>>,
+    open_container: Res<OpenContainer>,
+    mut inventories: Query<&mut Inventory>,
+    names: Query<&Name>,
+) {
+    let player = *player;
+
+    for (interaction, slot) in slots.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let other = match slot.owner == player {
+            true => open_container.0,
+            false => Some(player),
+        };
+        let Some(other) = other else {
+            continue;
+        };
+
+        let Ok([mut from, mut to]) = inventories.get_many_mut([slot.owner, other]) else {
+            continue;
+        };
+
+        if !to.is_full() {
+            if let Some(item) = from.remove_item(slot.slot) {
+                let _ = to.insert_item(item);
+            }
+        }
+    }
+
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match *button {
+            InventoryButton::TakeAll => {
+                let Some(container) = open_container.0 else {
+                    continue;
+                };
+                let Ok([mut from, mut to]) = inventories.get_many_mut([container, player]) else {
+                    continue;
+                };
 
-    info!(?inventory);
+                for slot in 0..from.capacity() {
+                    if to.is_full() {
+                        break;
+                    }
+                    if let Some(item) = from.remove_item(slot) {
+                        let _ = to.insert_item(item);
+                    }
+                }
+            }
+            InventoryButton::Sort(owner) => {
+                let Ok(mut inventory) = inventories.get_mut(owner) else {
+                    continue;
+                };
+
+                inventory.sort_by_key(|&item| {
+                    names
+                        .get(item)
+                        .map_or(String::new(), |name| name.to_string())
+                });
+            }
+        }
+    }
 }
@@ -1,16 +1,26 @@
 mod camera;
+mod camera_effects;
 pub(in crate::gameplay) mod controller;
 mod crosshair;
 mod interact;
 mod inventory;
+mod noclip;
+mod third_person;
 
+use crate::AppState;
+use crate::gameplay::Map;
 use crate::gameplay::items::Item;
 use crate::gameplay::items::inventory::{EquippedItem, Inventory};
 use crate::gameplay::player::controller::PlayerControllerBundle;
-use crate::gameplay::player::interact::InteractionRange;
+use crate::gameplay::player::interact::{InteractionRange, InteractionTarget};
 use crate::gameplay::player::inventory::Holding;
+use crate::gameplay::spawn::{SpawnPoints, Team};
+use crate::gameplay::streaming::StreamedCells;
+use crate::loading::GameAssets;
 use avian3d::prelude::ColliderConstructor::ConvexHullFromMesh;
-use avian3d::prelude::{Collider, ColliderConstructorHierarchy, RigidBody};
+use avian3d::prelude::{
+    Collider, ColliderConstructorHierarchy, ColliderConstructorHierarchyReady, RigidBody,
+};
 use bevy::prelude::*;
 use camera::PlayerCameraTarget;
 
@@ -18,24 +28,71 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(inventory::plugin)
         .add_plugins(controller::plugin)
         .add_plugins(camera::plugin)
+        .add_plugins(camera_effects::plugin)
         .add_plugins(crosshair::plugin)
-        .add_plugins(interact::plugin);
+        .add_plugins(interact::plugin)
+        .add_plugins(noclip::plugin)
+        .add_plugins(third_person::plugin);
 
-    app.add_systems(Startup, spawn_test_player);
+    app.init_resource::<PendingPlayerSpawn>();
+
+    app.add_observer(queue_test_player);
+
+    app.add_systems(
+        Update,
+        spawn_test_player.run_if(in_state(AppState::Gameplay)),
+    )
+    .add_systems(OnExit(AppState::Gameplay), clear_pending_player);
 }
 
 #[derive(Default, Component, Debug, Reflect)]
 #[reflect(Component)]
 pub struct Player;
 
-fn spawn_test_player(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let gun_handle =
-        asset_server.load(GltfAssetLabel::Scene(0).from_asset("weapons/basic_gun.glb"));
-    
+/// Where the player spawns once the ground there is ready.
+#[derive(Resource, Default, Debug)]
+struct PendingPlayerSpawn(Option<Transform>);
+
+/// Waits for the map's colliders, otherwise the player falls through the world before the
+/// trimesh is built.
+fn queue_test_player(
+    trigger: Trigger<ColliderConstructorHierarchyReady>,
+    maps: Query<(), With<Map>>,
+    spawn_points: SpawnPoints,
+    mut pending: ResMut<PendingPlayerSpawn>,
+) {
+    if !maps.contains(trigger.target()) {
+        return;
+    }
+
+    pending.0 = Some(spawn_points.choose(Team::default(), None));
+}
+
+fn clear_pending_player(mut pending: ResMut<PendingPlayerSpawn>) {
+    pending.0 = None;
+}
+
+/// Also waits for the streamed cell under the spawn point, which may still be loading after the
+/// map is ready.
+fn spawn_test_player(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut pending: ResMut<PendingPlayerSpawn>,
+    cells: StreamedCells,
+) {
+    let Some(transform) = pending.0 else {
+        return;
+    };
+    if !cells.loaded_at(transform.translation) {
+        return;
+    }
+    pending.0 = None;
+
     let gun = commands.spawn((
         Name::new("gun"),
+        StateScoped(AppState::Gameplay),
         Transform::from_xyz(5.0, 10.0, 0.0),
-        SceneRoot(gun_handle),
+        SceneRoot(assets.gun.clone()),
         ColliderConstructorHierarchy::new(ConvexHullFromMesh),
         RigidBody::Dynamic,
         Item,
@@ -44,14 +101,17 @@ fn spawn_test_player(mut commands: Commands, asset_server: Res<AssetServer>) {
     let player = commands
         .spawn((
             Name::new("Player"),
+            StateScoped(AppState::Gameplay),
             Player,
-            Transform::from_xyz(0.0, 15.0, 0.0),
+            Team::default(),
+            transform,
             PlayerControllerBundle::new(Collider::capsule_endpoints(
                 0.5,
                 Vec3::NEG_Y * 0.5,
                 Vec3::Y * 0.5,
             )),
             InteractionRange(5.0),
+            InteractionTarget::default(),
             EquippedItem(Some(gun)),
             Inventory::new(30),
         ))
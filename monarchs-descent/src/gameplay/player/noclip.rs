@@ -0,0 +1,231 @@
+use crate::AppState;
+use crate::gameplay::input::{
+    FlyDown, FlyUp, Move, PlayerActions, Sprint, ToggleNoclip, ToggleSpectator,
+};
+use crate::gameplay::player::Player;
+use crate::gameplay::player::camera::PlayerCamera;
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_console::{AddConsoleCommand, ConsoleCommand};
+use bevy_enhanced_input::prelude::*;
+use clap::Parser;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FlyMode>()
+        .register_type::<FlySettings>()
+        .register_type::<Flying>()
+        .register_type::<DetachedCamera>();
+
+    app.init_resource::<FlyMode>()
+        .init_resource::<FlySettings>();
+
+    app.add_observer(apply_fly_mode)
+        .add_observer(toggle_noclip)
+        .add_observer(toggle_spectator);
+
+    app.add_console_command::<NoclipCommand, _>(noclip_command)
+        .add_console_command::<SpectatorCommand, _>(spectator_command);
+
+    app.add_systems(Update, (fly_noclip, fly_spectator))
+        .add_systems(OnExit(AppState::Gameplay), reset_fly_mode);
+}
+
+#[derive(Resource, Reflect, Default, Debug, Copy, Clone, PartialEq)]
+#[reflect(Resource)]
+pub enum FlyMode {
+    #[default]
+    Off,
+    /// The player body flies through the map with its collider disabled.
+    Noclip,
+    /// The camera detaches and flies on its own, leaving the player body where it was.
+    Spectator,
+}
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct FlySettings {
+    pub speed: f32,
+    pub fast_multiplier: f32,
+}
+
+impl Default for FlySettings {
+    fn default() -> Self {
+        Self {
+            speed: 10.0,
+            fast_multiplier: 4.0,
+        }
+    }
+}
+
+/// Movement input drives the fly camera instead of the player controller.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct Flying;
+
+/// The [`PlayerCamera`] no longer follows the player.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+pub struct DetachedCamera;
+
+#[derive(Event, Debug)]
+pub struct SetFlyMode(pub FlyMode);
+
+/// Toggle noclip, flying the player through the map
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "noclip")]
+struct NoclipCommand;
+
+/// Toggle the detached spectator camera
+#[derive(Parser, ConsoleCommand)]
+#[command(name = "spectator")]
+struct SpectatorCommand;
+
+/// The player is despawned with the world, so the next one starts on foot.
+fn reset_fly_mode(mut mode: ResMut<FlyMode>) {
+    *mode = FlyMode::Off;
+}
+
+fn toggled(current: FlyMode, mode: FlyMode) -> FlyMode {
+    match current == mode {
+        true => FlyMode::Off,
+        false => mode,
+    }
+}
+
+fn toggle_noclip(
+    _trigger: Trigger<Started<ToggleNoclip>>,
+    mut commands: Commands,
+    mode: Res<FlyMode>,
+) {
+    commands.trigger(SetFlyMode(toggled(*mode, FlyMode::Noclip)));
+}
+
+fn toggle_spectator(
+    _trigger: Trigger<Started<ToggleSpectator>>,
+    mut commands: Commands,
+    mode: Res<FlyMode>,
+) {
+    commands.trigger(SetFlyMode(toggled(*mode, FlyMode::Spectator)));
+}
+
+fn noclip_command(
+    mut command: ConsoleCommand<NoclipCommand>,
+    mut commands: Commands,
+    mode: Res<FlyMode>,
+) {
+    if let Some(Ok(NoclipCommand)) = command.take() {
+        let mode = toggled(*mode, FlyMode::Noclip);
+        commands.trigger(SetFlyMode(mode));
+        command.reply_ok(format!("fly mode {mode:?}"));
+    }
+}
+
+fn spectator_command(
+    mut command: ConsoleCommand<SpectatorCommand>,
+    mut commands: Commands,
+    mode: Res<FlyMode>,
+) {
+    if let Some(Ok(SpectatorCommand)) = command.take() {
+        let mode = toggled(*mode, FlyMode::Spectator);
+        commands.trigger(SetFlyMode(mode));
+        command.reply_ok(format!("fly mode {mode:?}"));
+    }
+}
+
+fn apply_fly_mode(
+    trigger: Trigger<SetFlyMode>,
+    mut commands: Commands,
+    mut mode: ResMut<FlyMode>,
+    player: Single<Entity, With<Player>>,
+    camera: Single<Entity, With<PlayerCamera>>,
+) {
+    let next = trigger.0;
+    if *mode == next {
+        return;
+    }
+
+    match *mode {
+        FlyMode::Off => {}
+        FlyMode::Noclip => {
+            commands
+                .entity(*player)
+                .remove::<(Flying, ColliderDisabled)>()
+                .insert((RigidBody::Dynamic, LinearVelocity::ZERO));
+        }
+        FlyMode::Spectator => {
+            commands
+                .entity(*player)
+                .remove::<(Flying, RigidBodyDisabled)>();
+            commands.entity(*camera).remove::<DetachedCamera>();
+        }
+    }
+
+    match next {
+        FlyMode::Off => {}
+        FlyMode::Noclip => {
+            commands
+                .entity(*player)
+                .insert((Flying, ColliderDisabled, RigidBody::Kinematic));
+        }
+        FlyMode::Spectator => {
+            // The body is left frozen where it was rather than sliding or falling unattended.
+            commands
+                .entity(*player)
+                .insert((Flying, RigidBodyDisabled, LinearVelocity::ZERO));
+            commands.entity(*camera).insert(DetachedCamera);
+        }
+    }
+
+    info!(from = ?*mode, to = ?next, "changing fly mode");
+    *mode = next;
+}
+
+fn fly_velocity(actions: &Actions<PlayerActions>, rotation: Quat, settings: &FlySettings) -> Vec3 {
+    let movement = actions.action::<Move>().value().as_axis2d();
+
+    let mut vertical = 0.0;
+    if actions.action::<FlyUp>().state() == ActionState::Fired {
+        vertical += 1.0;
+    }
+    if actions.action::<FlyDown>().state() == ActionState::Fired {
+        vertical -= 1.0;
+    }
+
+    let speed = match actions.action::<Sprint>().state() == ActionState::Fired {
+        true => settings.speed * settings.fast_multiplier,
+        false => settings.speed,
+    };
+
+    let direction = rotation * Vec3::new(movement.x, 0.0, -movement.y) + Vec3::Y * vertical;
+    direction.normalize_or_zero() * speed
+}
+
+fn fly_noclip(
+    mode: Res<FlyMode>,
+    settings: Res<FlySettings>,
+    actions: Single<&Actions<PlayerActions>>,
+    camera: Single<&Transform, With<PlayerCamera>>,
+    mut player: Single<&mut LinearVelocity, (With<Player>, With<Flying>)>,
+) {
+    if *mode != FlyMode::Noclip {
+        return;
+    }
+
+    player.0 = fly_velocity(&actions, camera.rotation, &settings);
+}
+
+fn fly_spectator(
+    time: Res<Time>,
+    mode: Res<FlyMode>,
+    settings: Res<FlySettings>,
+    actions: Single<&Actions<PlayerActions>>,
+    mut camera: Single<&mut Transform, (With<PlayerCamera>, With<DetachedCamera>)>,
+) {
+    if *mode != FlyMode::Spectator {
+        return;
+    }
+
+    camera.translation += fly_velocity(&actions, camera.rotation, &settings) * time.delta_secs();
+}
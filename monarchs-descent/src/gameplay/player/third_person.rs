@@ -0,0 +1,215 @@
+use crate::gameplay::input::{SwapShoulder, ToggleCameraMode};
+use crate::gameplay::player::Player;
+use crate::gameplay::player::camera::{
+    PlayerCamera, ViewModelCamera, sync_camera_to_player_transform,
+};
+use crate::gameplay::player::camera_effects::PlayerView;
+use crate::gameplay::player::noclip::DetachedCamera;
+use crate::{DEFAULT_RENDER_LAYER, PLAYER_BODY_RENDER_LAYER};
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+use bevy_enhanced_input::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CameraMode>()
+        .register_type::<SpringArm>()
+        .register_type::<Shoulder>()
+        .register_type::<PlayerBody>();
+
+    app.add_observer(toggle_camera_mode)
+        .add_observer(swap_shoulder)
+        .add_observer(spawn_player_body);
+
+    app.add_systems(Update, apply_camera_mode);
+
+    app.add_systems(
+        PostUpdate,
+        update_spring_arm
+            .after(sync_camera_to_player_transform)
+            .before(TransformSystem::TransformPropagate),
+    );
+}
+
+#[derive(Default, Component, Reflect, Debug, Copy, Clone, PartialEq)]
+#[reflect(Component)]
+pub enum CameraMode {
+    #[default]
+    FirstPerson,
+    ThirdPerson,
+}
+
+#[derive(Default, Reflect, Debug, Copy, Clone, PartialEq)]
+pub enum Shoulder {
+    #[default]
+    Right,
+    Left,
+}
+
+impl Shoulder {
+    fn side(self) -> f32 {
+        match self {
+            Shoulder::Right => 1.0,
+            Shoulder::Left => -1.0,
+        }
+    }
+}
+
+/// Pulls the [`PlayerCamera`] back over the shoulder in third person, shortening when the map gets
+/// in the way so the camera never ends up inside geometry.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct SpringArm {
+    pub length: f32,
+    pub shoulder_offset: Vec2,
+    pub shoulder: Shoulder,
+    pub probe_radius: f32,
+    pub smoothing: f32,
+    extension: f32,
+    side: f32,
+}
+
+impl Default for SpringArm {
+    fn default() -> Self {
+        Self {
+            length: 3.0,
+            shoulder_offset: Vec2::new(0.6, 0.3),
+            shoulder: Shoulder::default(),
+            probe_radius: 0.2,
+            smoothing: 10.0,
+            extension: 0.0,
+            side: Shoulder::default().side(),
+        }
+    }
+}
+
+/// The visible player mesh, only rendered by the world camera in third person.
+#[derive(Default, Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct PlayerBody;
+
+fn toggle_camera_mode(
+    _trigger: Trigger<Started<ToggleCameraMode>>,
+    mut mode: Single<&mut CameraMode, With<PlayerCamera>>,
+) {
+    **mode = match **mode {
+        CameraMode::FirstPerson => CameraMode::ThirdPerson,
+        CameraMode::ThirdPerson => CameraMode::FirstPerson,
+    };
+
+    debug!(mode = ?**mode, "switched camera mode");
+}
+
+fn swap_shoulder(
+    _trigger: Trigger<Started<SwapShoulder>>,
+    mut arm: Single<&mut SpringArm, With<PlayerCamera>>,
+) {
+    arm.shoulder = match arm.shoulder {
+        Shoulder::Right => Shoulder::Left,
+        Shoulder::Left => Shoulder::Right,
+    };
+}
+
+fn spawn_player_body(
+    trigger: Trigger<OnAdd, Player>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.entity(trigger.target()).with_child((
+        Name::new("Player Body"),
+        PlayerBody,
+        Mesh3d(meshes.add(Capsule3d::new(0.5, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        RenderLayers::layer(PLAYER_BODY_RENDER_LAYER),
+    ));
+}
+
+fn apply_camera_mode(
+    mode: Single<&CameraMode, (With<PlayerCamera>, Changed<CameraMode>)>,
+    mut world_cameras: Query<&mut RenderLayers, (With<PlayerView>, Without<ViewModelCamera>)>,
+    mut view_model_cameras: Query<&mut Camera, With<ViewModelCamera>>,
+) {
+    let third_person = **mode == CameraMode::ThirdPerson;
+
+    for mut layers in world_cameras.iter_mut() {
+        *layers = match third_person {
+            true => RenderLayers::from_layers(&[DEFAULT_RENDER_LAYER, PLAYER_BODY_RENDER_LAYER]),
+            false => RenderLayers::layer(DEFAULT_RENDER_LAYER),
+        };
+    }
+
+    for mut camera in view_model_cameras.iter_mut() {
+        camera.is_active = !third_person;
+    }
+}
+
+fn update_spring_arm(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    camera: Single<
+        (&mut Transform, &CameraMode, &mut SpringArm),
+        (With<PlayerCamera>, Without<DetachedCamera>),
+    >,
+    player: Single<(Entity, &RigidBodyColliders), With<Player>>,
+    colliders: Query<(Option<&ColliderOf>, Has<Sensor>)>,
+    bodies: Query<&RigidBody>,
+) {
+    let (mut transform, mode, mut arm) = camera.into_inner();
+    let (player, player_colliders) = player.into_inner();
+    let blend = 1.0 - (-arm.smoothing * time.delta_secs()).exp();
+
+    arm.side = arm.side.lerp(arm.shoulder.side(), blend);
+
+    let target_extension = match mode {
+        CameraMode::FirstPerson => 0.0,
+        CameraMode::ThirdPerson => 1.0,
+    };
+
+    let pivot = transform.translation;
+    let offset = transform.rotation
+        * Vec3::new(
+            arm.shoulder_offset.x * arm.side,
+            arm.shoulder_offset.y,
+            arm.length,
+        );
+
+    let Ok((direction, distance)) = Dir3::new_and_length(offset) else {
+        return;
+    };
+
+    // Only level geometry pulls the arm in, loose items and trigger volumes would make it snap
+    // back and forth. Colliders without a body are static too.
+    let filter = SpatialQueryFilter::from_excluded_entities(
+        std::iter::once(player).chain(player_colliders.iter()),
+    );
+    let is_level_geometry = |entity: Entity| match colliders.get(entity) {
+        Ok((_, true)) => false,
+        Ok((Some(collider_of), false)) => bodies
+            .get(collider_of.body)
+            .is_ok_and(|body| *body == RigidBody::Static),
+        Ok((None, false)) | Err(_) => true,
+    };
+
+    let allowed = spatial_query
+        .cast_shape_predicate(
+            &Collider::sphere(arm.probe_radius),
+            pivot,
+            Quat::IDENTITY,
+            direction,
+            &ShapeCastConfig::from_max_distance(distance),
+            &filter,
+            &is_level_geometry,
+        )
+        .map_or(1.0, |hit| hit.distance / distance);
+
+    let target_extension = target_extension.min(allowed);
+
+    // Snap in immediately when something is in the way, only ease back out once it is clear.
+    arm.extension = match target_extension < arm.extension {
+        true => target_extension,
+        false => arm.extension.lerp(target_extension, blend),
+    };
+
+    transform.translation = pivot + offset * arm.extension;
+}
@@ -0,0 +1,488 @@
+use crate::gameplay::settings::config::GameSettings;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::any::type_name;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<InputBinding>()
+        .register_type::<BindingContext>();
+
+    app.init_resource::<DefaultBindings>()
+        .init_resource::<ReservedInputs>();
+}
+
+/// Keyboard and gamepad input bound to one action, one column per device.
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct InputBinding {
+    pub keyboard: Option<KeyCode>,
+    pub gamepad: Option<GamepadButton>,
+}
+
+impl InputBinding {
+    pub(crate) fn new(keyboard: KeyCode, gamepad: GamepadButton) -> Self {
+        Self {
+            keyboard: Some(keyboard),
+            gamepad: Some(gamepad),
+        }
+    }
+
+    pub(crate) fn keyboard(keyboard: KeyCode) -> Self {
+        Self {
+            keyboard: Some(keyboard),
+            gamepad: None,
+        }
+    }
+}
+
+/// Input context an action is bound in, the same input may only be used once per context.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingContext {
+    Player,
+    Inventory,
+    Pause,
+    Settings,
+    Editor,
+}
+
+impl BindingContext {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            BindingContext::Player => "Player",
+            BindingContext::Inventory => "Inventory",
+            BindingContext::Pause => "Pause menu",
+            BindingContext::Settings => "Menus",
+            BindingContext::Editor => "Editor",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct DefaultBinding {
+    pub id: &'static str,
+    pub context: BindingContext,
+    pub label: &'static str,
+    pub binding: InputBinding,
+}
+
+/// Every rebindable action in registration order, the player's changes are stored in
+/// [`GameSettings`] as overrides on top of these.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct DefaultBindings(pub Vec<DefaultBinding>);
+
+/// An input hard-coded next to the rebindable ones, e.g. the fixed menu navigation keys.
+#[derive(Debug)]
+pub(crate) struct ReservedInput {
+    pub id: &'static str,
+    pub context: BindingContext,
+    pub label: &'static str,
+    pub input: CapturedInput,
+}
+
+/// Fixed inputs that rebinding an action in the same context to is refused, they can't be
+/// swapped out of the way.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct ReservedInputs(pub Vec<ReservedInput>);
+
+/// Key for `A` in the binding table.
+pub(crate) fn binding_id<A: 'static>() -> &'static str {
+    let name = type_name::<A>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+pub(crate) trait RebindAppExt {
+    /// Makes `A` rebindable from the settings menu, `A` is usually the action itself but can be
+    /// any type naming one part of a composite binding.
+    fn add_rebindable<A: 'static>(
+        &mut self,
+        context: BindingContext,
+        label: &'static str,
+        binding: InputBinding,
+    ) -> &mut Self;
+
+    /// Marks `inputs`, bound to `A` outside the binding table, as taken in `context`.
+    fn reserve_inputs<A: 'static>(
+        &mut self,
+        context: BindingContext,
+        label: &'static str,
+        inputs: impl IntoIterator<Item = CapturedInput>,
+    ) -> &mut Self;
+}
+
+impl RebindAppExt for App {
+    fn add_rebindable<A: 'static>(
+        &mut self,
+        context: BindingContext,
+        label: &'static str,
+        binding: InputBinding,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<DefaultBindings>()
+            .0
+            .push(DefaultBinding {
+                id: binding_id::<A>(),
+                context,
+                label,
+                binding,
+            });
+        self
+    }
+
+    fn reserve_inputs<A: 'static>(
+        &mut self,
+        context: BindingContext,
+        label: &'static str,
+        inputs: impl IntoIterator<Item = CapturedInput>,
+    ) -> &mut Self {
+        let reserved = inputs.into_iter().map(|input| ReservedInput {
+            id: binding_id::<A>(),
+            context,
+            label,
+            input,
+        });
+        self.world_mut()
+            .get_resource_or_init::<ReservedInputs>()
+            .0
+            .extend(reserved);
+        self
+    }
+}
+
+/// Current bindings, the player's override if there is one and the default otherwise.
+#[derive(SystemParam)]
+pub(crate) struct InputBindings<'w> {
+    defaults: Res<'w, DefaultBindings>,
+    settings: Res<'w, GameSettings>,
+}
+
+impl InputBindings<'_> {
+    pub(crate) fn get<A: 'static>(&self) -> InputBinding {
+        lookup(&self.defaults, &self.settings, binding_id::<A>())
+    }
+}
+
+pub(crate) fn lookup(
+    defaults: &DefaultBindings,
+    settings: &GameSettings,
+    id: &str,
+) -> InputBinding {
+    settings
+        .controls
+        .bindings
+        .get(id)
+        .copied()
+        .or_else(|| {
+            defaults
+                .0
+                .iter()
+                .find(|default| default.id == id)
+                .map(|default| default.binding)
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) trait BindInputs {
+    fn to_binding(&mut self, binding: InputBinding) -> &mut Self;
+}
+
+impl BindInputs for ActionBinding {
+    fn to_binding(&mut self, binding: InputBinding) -> &mut Self {
+        if let Some(key) = binding.keyboard {
+            self.to(key);
+        }
+        if let Some(button) = binding.gamepad {
+            self.to(button);
+        }
+        self
+    }
+}
+
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BindingDevice {
+    #[default]
+    Keyboard,
+    Gamepad,
+}
+
+/// Input captured for one device column of a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CapturedInput {
+    Key(KeyCode),
+    Button(GamepadButton),
+}
+
+impl CapturedInput {
+    fn device(self) -> BindingDevice {
+        match self {
+            CapturedInput::Key(_) => BindingDevice::Keyboard,
+            CapturedInput::Button(_) => BindingDevice::Gamepad,
+        }
+    }
+
+    fn matches(self, binding: InputBinding) -> bool {
+        match self {
+            CapturedInput::Key(key) => binding.keyboard == Some(key),
+            CapturedInput::Button(button) => binding.gamepad == Some(button),
+        }
+    }
+
+    fn apply(self, binding: &mut InputBinding) {
+        match self {
+            CapturedInput::Key(key) => binding.keyboard = Some(key),
+            CapturedInput::Button(button) => binding.gamepad = Some(button),
+        }
+    }
+}
+
+fn copy_column(from: InputBinding, to: &mut InputBinding, device: BindingDevice) {
+    match device {
+        BindingDevice::Keyboard => to.keyboard = from.keyboard,
+        BindingDevice::Gamepad => to.gamepad = from.gamepad,
+    }
+}
+
+/// What [`rebind`] did with the captured input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rebound {
+    Bound,
+    /// The action with this label used the input and got the old input instead.
+    Swapped(&'static str),
+    /// The input is fixed to the action with this label, nothing changed.
+    Reserved(&'static str),
+}
+
+/// Binds `input` to the default binding at `index`. An action in the same context already using
+/// the input gets the old input of the rebound action instead, unless the input is reserved.
+pub(crate) fn rebind(
+    defaults: &DefaultBindings,
+    reserved: &ReservedInputs,
+    settings: &mut GameSettings,
+    index: usize,
+    input: CapturedInput,
+) -> Option<Rebound> {
+    let entry = defaults.0.get(index)?;
+
+    let taken = reserved.0.iter().find(|reserved| {
+        reserved.id != entry.id && reserved.context == entry.context && reserved.input == input
+    });
+    if let Some(taken) = taken {
+        return Some(Rebound::Reserved(taken.label));
+    }
+
+    let previous = lookup(defaults, settings, entry.id);
+
+    let conflict = defaults.0.iter().find(|other| {
+        other.id != entry.id
+            && other.context == entry.context
+            && input.matches(lookup(defaults, settings, other.id))
+    });
+
+    if let Some(conflict) = conflict {
+        let mut swapped = lookup(defaults, settings, conflict.id);
+        copy_column(previous, &mut swapped, input.device());
+        settings
+            .controls
+            .bindings
+            .insert(conflict.id.to_string(), swapped);
+    }
+
+    let mut binding = previous;
+    input.apply(&mut binding);
+    settings
+        .controls
+        .bindings
+        .insert(entry.id.to_string(), binding);
+
+    Some(match conflict {
+        Some(conflict) => Rebound::Swapped(conflict.label),
+        None => Rebound::Bound,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defaults() -> DefaultBindings {
+        let binding = |id, context, binding| DefaultBinding {
+            id,
+            context,
+            label: id,
+            binding,
+        };
+        DefaultBindings(vec![
+            binding(
+                "Jump",
+                BindingContext::Player,
+                InputBinding::new(KeyCode::Space, GamepadButton::South),
+            ),
+            binding(
+                "Crouch",
+                BindingContext::Player,
+                InputBinding::new(KeyCode::ControlLeft, GamepadButton::RightThumb),
+            ),
+            binding(
+                "MenuConfirm",
+                BindingContext::Settings,
+                InputBinding::new(KeyCode::Enter, GamepadButton::South),
+            ),
+        ])
+    }
+
+    fn reserved() -> ReservedInputs {
+        ReservedInputs(vec![
+            ReservedInput {
+                id: "MenuUp",
+                context: BindingContext::Settings,
+                label: "Menu up",
+                input: CapturedInput::Key(KeyCode::KeyW),
+            },
+            ReservedInput {
+                id: "FlyUp",
+                context: BindingContext::Player,
+                label: "Fly up",
+                input: CapturedInput::Button(GamepadButton::RightTrigger),
+            },
+        ])
+    }
+
+    #[test]
+    fn rebind_without_conflict_only_changes_the_device_column() {
+        let defaults = defaults();
+        let reserved = reserved();
+        let mut settings = GameSettings::default();
+
+        let rebound = rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            0,
+            CapturedInput::Key(KeyCode::KeyJ),
+        );
+
+        assert_eq!(rebound, Some(Rebound::Bound));
+        assert_eq!(
+            lookup(&defaults, &settings, "Jump"),
+            InputBinding::new(KeyCode::KeyJ, GamepadButton::South)
+        );
+        assert!(!settings.controls.bindings.contains_key("Crouch"));
+    }
+
+    #[test]
+    fn rebind_conflict_swaps_the_inputs() {
+        let defaults = defaults();
+        let reserved = reserved();
+        let mut settings = GameSettings::default();
+
+        let rebound = rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            0,
+            CapturedInput::Key(KeyCode::ControlLeft),
+        );
+
+        assert_eq!(rebound, Some(Rebound::Swapped("Crouch")));
+        assert_eq!(
+            lookup(&defaults, &settings, "Jump"),
+            InputBinding::new(KeyCode::ControlLeft, GamepadButton::South)
+        );
+        assert_eq!(
+            lookup(&defaults, &settings, "Crouch"),
+            InputBinding::new(KeyCode::Space, GamepadButton::RightThumb)
+        );
+    }
+
+    #[test]
+    fn rebind_ignores_other_contexts() {
+        let defaults = defaults();
+        let reserved = reserved();
+        let mut settings = GameSettings::default();
+
+        let rebound = rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            0,
+            CapturedInput::Key(KeyCode::Enter),
+        );
+
+        assert_eq!(rebound, Some(Rebound::Bound));
+        assert_eq!(
+            lookup(&defaults, &settings, "MenuConfirm"),
+            InputBinding::new(KeyCode::Enter, GamepadButton::South)
+        );
+    }
+
+    #[test]
+    fn rebind_gamepad_swap_keeps_the_keyboard_column() {
+        let defaults = defaults();
+        let reserved = reserved();
+        let mut settings = GameSettings::default();
+
+        rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            1,
+            CapturedInput::Button(GamepadButton::South),
+        );
+
+        assert_eq!(
+            lookup(&defaults, &settings, "Crouch"),
+            InputBinding::new(KeyCode::ControlLeft, GamepadButton::South)
+        );
+        assert_eq!(
+            lookup(&defaults, &settings, "Jump"),
+            InputBinding::new(KeyCode::Space, GamepadButton::RightThumb)
+        );
+    }
+
+    #[test]
+    fn rebind_to_a_reserved_input_is_refused() {
+        let defaults = defaults();
+        let reserved = reserved();
+        let mut settings = GameSettings::default();
+
+        let rebound = rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            2,
+            CapturedInput::Key(KeyCode::KeyW),
+        );
+        assert_eq!(rebound, Some(Rebound::Reserved("Menu up")));
+
+        let rebound = rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            0,
+            CapturedInput::Button(GamepadButton::RightTrigger),
+        );
+        assert_eq!(rebound, Some(Rebound::Reserved("Fly up")));
+        assert!(settings.controls.bindings.is_empty());
+    }
+
+    #[test]
+    fn reserved_inputs_only_apply_to_their_context() {
+        let defaults = defaults();
+        let reserved = reserved();
+        let mut settings = GameSettings::default();
+
+        let rebound = rebind(
+            &defaults,
+            &reserved,
+            &mut settings,
+            0,
+            CapturedInput::Key(KeyCode::KeyW),
+        );
+
+        assert_eq!(rebound, Some(Rebound::Bound));
+        assert_eq!(
+            lookup(&defaults, &settings, "Jump"),
+            InputBinding::new(KeyCode::KeyW, GamepadButton::South)
+        );
+    }
+}
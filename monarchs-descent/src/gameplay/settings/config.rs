@@ -0,0 +1,332 @@
+use crate::gameplay::settings::bindings::InputBinding;
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+/// Bump when a change to [`GameSettings`] needs more than new fields with defaults, and add a
+/// step to [`GameSettings::migrate`].
+const SETTINGS_VERSION: u32 = 1;
+const SETTINGS_FILE: &str = "settings.ron";
+/// Edits are batched so dragging a slider doesn't write the file every frame.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GameSettings>()
+        .register_type::<ControlSettings>()
+        .register_type::<VideoSettings>()
+        .register_type::<AudioSettings>()
+        .register_type::<GameplaySettings>()
+        .register_type::<WindowModeSetting>()
+        .register_type::<MsaaSetting>()
+        .register_type::<ShadowQuality>();
+
+    app.insert_resource(GameSettings::load())
+        .init_resource::<PendingSave>();
+
+    app.add_systems(
+        Update,
+        (
+            (schedule_save, apply_volume).run_if(resource_changed::<GameSettings>),
+            save_settings,
+        )
+            .chain(),
+    )
+    .add_systems(Last, flush_settings.run_if(on_event::<AppExit>));
+}
+
+/// Player facing settings, persisted as RON in the platform config directory.
+#[derive(Resource, Reflect, Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameSettings {
+    /// Format version of the file this was loaded from, see [`GameSettings::migrate`].
+    pub version: u32,
+    pub controls: ControlSettings,
+    pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub gameplay: GameplaySettings,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ControlSettings {
+    /// Radians of camera rotation per pixel of mouse motion.
+    pub sensitivity: f32,
+    pub invert_y: bool,
+    /// Rebound actions by binding id, everything else uses its default binding.
+    pub bindings: BTreeMap<String, InputBinding>,
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.002,
+            invert_y: false,
+            bindings: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct VideoSettings {
+    /// Vertical field of view in degrees.
+    pub fov: f32,
+    pub window_mode: WindowModeSetting,
+    /// Physical window size, only used when windowed.
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+    /// Frame rate cap, uncapped when `None`.
+    pub frame_limit: Option<f64>,
+    pub msaa: MsaaSetting,
+    pub shadow_quality: ShadowQuality,
+    pub show_fps: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            fov: 45.0,
+            window_mode: WindowModeSetting::BorderlessFullscreen,
+            resolution: (1920, 1080),
+            vsync: true,
+            frame_limit: None,
+            msaa: MsaaSetting::Sample4,
+            shadow_quality: ShadowQuality::Medium,
+            show_fps: true,
+        }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowModeSetting {
+    Windowed,
+    #[default]
+    BorderlessFullscreen,
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub const ALL: [Self; 3] = [Self::Windowed, Self::BorderlessFullscreen, Self::Fullscreen];
+}
+
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsaaSetting {
+    Off,
+    Sample2,
+    #[default]
+    Sample4,
+    Sample8,
+}
+
+impl MsaaSetting {
+    pub const ALL: [Self; 4] = [Self::Off, Self::Sample2, Self::Sample4, Self::Sample8];
+}
+
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowQuality {
+    Off,
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl ShadowQuality {
+    pub const ALL: [Self; 4] = [Self::Off, Self::Low, Self::Medium, Self::High];
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    /// Linear master volume, `1.0` plays audio at its original level.
+    pub master_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self { master_volume: 1.0 }
+    }
+}
+
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct GameplaySettings {
+    /// Crosshair width as a percentage of the screen width.
+    pub crosshair_size: f32,
+    pub camera_shake: bool,
+    pub headbob: bool,
+    pub landing_dip: bool,
+    /// Widens the field of view while sprinting.
+    pub fov_kick: bool,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            crosshair_size: 2.0,
+            camera_shake: true,
+            headbob: true,
+            landing_dip: true,
+            fov_kick: true,
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct PendingSave(Option<Timer>);
+
+impl GameSettings {
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("monarchs-descent").join(SETTINGS_FILE))
+    }
+
+    /// Reads the settings file, falling back to defaults if it is missing or unreadable.
+    fn load() -> Self {
+        let Some(path) = Self::path() else {
+            warn!("no config directory, using default settings");
+            return Self::current_defaults();
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                info!(?path, "no settings file, using defaults");
+                return Self::current_defaults();
+            }
+            Err(error) => {
+                error!(?path, %error, "failed to read settings, using defaults");
+                return Self::current_defaults();
+            }
+        };
+
+        match ron::from_str::<Self>(&contents) {
+            Ok(settings) => {
+                info!(?path, "loaded settings");
+                settings.migrate()
+            }
+            Err(error) => {
+                error!(?path, %error, "failed to parse settings, using defaults");
+                Self::current_defaults()
+            }
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = Self::path().ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+
+        let contents = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(|error| error.to_string())?;
+        fs::write(&path, contents).map_err(|error| error.to_string())
+    }
+
+    fn current_defaults() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            ..default()
+        }
+    }
+
+    /// Upgrades settings written by older builds one version at a time. Fields missing from the
+    /// file have already been filled with defaults by serde.
+    fn migrate(mut self) -> Self {
+        if self.version > SETTINGS_VERSION {
+            warn!(
+                version = self.version,
+                "settings file is from a newer build, unknown fields are dropped"
+            );
+        }
+
+        while self.version < SETTINGS_VERSION {
+            match self.version {
+                // Unversioned files predate the version field and need no changes.
+                0 => {}
+                version => unreachable!("no settings migration from version {version}"),
+            }
+
+            self.version += 1;
+            debug!(version = self.version, "migrated settings");
+        }
+
+        self.version = SETTINGS_VERSION;
+        self
+    }
+}
+
+fn schedule_save(mut pending: ResMut<PendingSave>) {
+    pending.0 = Some(Timer::new(SAVE_DELAY, TimerMode::Once));
+}
+
+/// Ticks in real time, virtual time is frozen while the settings are open from the pause menu.
+fn save_settings(
+    time: Res<Time<Real>>,
+    settings: Res<GameSettings>,
+    mut pending: ResMut<PendingSave>,
+) {
+    let Some(timer) = pending.0.as_mut() else {
+        return;
+    };
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+    pending.0 = None;
+
+    write_settings(&settings);
+}
+
+/// Writes a save that is still waiting out its delay, so quitting right after an edit keeps it.
+fn flush_settings(settings: Res<GameSettings>, mut pending: ResMut<PendingSave>) {
+    if pending.0.take().is_some() {
+        write_settings(&settings);
+    }
+}
+
+fn write_settings(settings: &GameSettings) {
+    match settings.save() {
+        Ok(()) => debug!("saved settings"),
+        Err(error) => error!(%error, "failed to save settings"),
+    }
+}
+
+fn apply_volume(settings: Res<GameSettings>, mut volume: ResMut<GlobalVolume>) {
+    volume.volume = Volume::Linear(settings.audio.master_volume);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unversioned_file_migrates_to_the_current_version() {
+        let settings = ron::from_str::<GameSettings>("(controls: (invert_y: true))").unwrap();
+        assert_eq!(settings.version, 0);
+
+        let settings = settings.migrate();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert!(settings.controls.invert_y);
+        assert_eq!(settings.video, VideoSettings::default());
+    }
+
+    #[test]
+    fn current_version_is_unchanged() {
+        let settings = GameSettings::current_defaults();
+        assert_eq!(settings.clone().migrate(), settings);
+    }
+
+    #[test]
+    fn newer_version_is_read_as_the_current_one() {
+        let settings = GameSettings {
+            version: SETTINGS_VERSION + 1,
+            ..GameSettings::current_defaults()
+        };
+        assert_eq!(settings.migrate(), GameSettings::current_defaults());
+    }
+}
@@ -0,0 +1,877 @@
+use crate::GameState;
+use crate::gameplay::input::{
+    MenuConfirm, MenuDown, MenuLeft, MenuRight, MenuUp, NextTab, PreviousTab,
+};
+use crate::gameplay::settings::bindings::{BindingDevice, DefaultBindings};
+use crate::gameplay::settings::config::{
+    GameSettings, MsaaSetting, ShadowQuality, WindowModeSetting,
+};
+use crate::gameplay::settings::rebind;
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use bevy_enhanced_input::prelude::*;
+
+const ROW_WIDTH: f32 = 560.0;
+const SLIDER_WIDTH: f32 = 200.0;
+
+pub(super) const FOCUSED_COLOR: Color = Color::srgb(0.25, 0.25, 0.32);
+pub(super) const UNFOCUSED_COLOR: Color = Color::NONE;
+pub(super) const SELECTED_TAB_COLOR: Color = Color::srgb(0.4, 0.4, 0.5);
+pub(super) const TAB_COLOR: Color = Color::srgb(0.2, 0.2, 0.24);
+const HINT: &str = "Q/E switch tabs, arrows to change, Esc to close";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SettingsMenu>()
+        .register_type::<SettingsTab>();
+
+    app.init_resource::<SettingsMenu>();
+
+    app.add_observer(focus_previous)
+        .add_observer(focus_next)
+        .add_observer(decrease_setting)
+        .add_observer(increase_setting)
+        .add_observer(confirm_setting)
+        .add_observer(next_tab)
+        .add_observer(previous_tab);
+
+    app.add_systems(OnEnter(GameState::Settings), spawn_settings_screen);
+
+    app.add_systems(
+        Update,
+        (
+            handle_settings_mouse,
+            rebind::handle_binding_mouse,
+            rebind::capture_binding,
+            rebuild_settings_content,
+            update_settings_widgets,
+            rebind::update_binding_widgets,
+        )
+            .chain()
+            .run_if(in_state(GameState::Settings)),
+    );
+}
+
+#[derive(Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SettingsTab {
+    #[default]
+    Controls,
+    Bindings,
+    Video,
+    Audio,
+    Gameplay,
+}
+
+impl SettingsTab {
+    const ALL: [Self; 5] = [
+        Self::Controls,
+        Self::Bindings,
+        Self::Video,
+        Self::Audio,
+        Self::Gameplay,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SettingsTab::Controls => "Controls",
+            SettingsTab::Bindings => "Bindings",
+            SettingsTab::Video => "Video",
+            SettingsTab::Audio => "Audio",
+            SettingsTab::Gameplay => "Gameplay",
+        }
+    }
+
+    fn settings(self) -> &'static [Setting] {
+        match self {
+            SettingsTab::Controls => CONTROLS,
+            SettingsTab::Bindings => &[],
+            SettingsTab::Video => VIDEO,
+            SettingsTab::Audio => AUDIO,
+            SettingsTab::Gameplay => GAMEPLAY,
+        }
+    }
+
+    fn cycle(self, direction: isize) -> Self {
+        let index = Self::ALL.iter().position(|&tab| tab == self).unwrap_or(0);
+        let len = Self::ALL.len() as isize;
+        Self::ALL[(index as isize + direction).rem_euclid(len) as usize]
+    }
+}
+
+/// Which tab and row the settings menu has focused.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub(super) struct SettingsMenu {
+    pub tab: SettingsTab,
+    pub row: usize,
+    /// Row of the open dropdown and the option highlighted in it.
+    dropdown: Option<(usize, usize)>,
+    /// Device column focused on the bindings tab.
+    pub column: BindingDevice,
+    /// Binding row and column waiting for an input, see [`rebind`].
+    pub capture: Option<rebind::Capture>,
+    /// Shown in place of the controls hint, e.g. after a rebind swapped two actions.
+    pub notice: Option<String>,
+}
+
+impl SettingsMenu {
+    fn focused(&self) -> Option<&'static Setting> {
+        self.tab.settings().get(self.row)
+    }
+
+    pub(super) fn row_count(&self, defaults: &DefaultBindings) -> usize {
+        match self.tab {
+            // One row per binding and the reset button.
+            SettingsTab::Bindings => defaults.0.len() + 1,
+            tab => tab.settings().len(),
+        }
+    }
+
+    /// Whether the menu is waiting for the player to press an input to bind.
+    pub(super) fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+}
+
+/// One editable entry of [`GameSettings`], reading and writing it through plain functions.
+struct Setting {
+    label: &'static str,
+    kind: SettingKind,
+}
+
+enum SettingKind {
+    Slider {
+        min: f32,
+        max: f32,
+        step: f32,
+        get: fn(&GameSettings) -> f32,
+        set: fn(&mut GameSettings, f32),
+        format: fn(f32) -> String,
+    },
+    Toggle {
+        get: fn(&GameSettings) -> bool,
+        set: fn(&mut GameSettings, bool),
+    },
+    Dropdown {
+        options: &'static [&'static str],
+        get: fn(&GameSettings) -> usize,
+        set: fn(&mut GameSettings, usize),
+    },
+}
+
+impl Setting {
+    fn display(&self, settings: &GameSettings) -> String {
+        match self.kind {
+            SettingKind::Slider { get, format, .. } => format(get(settings)),
+            SettingKind::Toggle { get, .. } => match get(settings) {
+                true => "On".to_string(),
+                false => "Off".to_string(),
+            },
+            SettingKind::Dropdown { options, get, .. } => options[get(settings)].to_string(),
+        }
+    }
+
+    /// Steps the value left or right, sliders by one step and dropdowns by one option.
+    fn adjust(&self, settings: &mut GameSettings, direction: isize) {
+        match self.kind {
+            SettingKind::Slider {
+                min,
+                max,
+                step,
+                get,
+                set,
+                ..
+            } => set(
+                settings,
+                (get(settings) + step * direction as f32).clamp(min, max),
+            ),
+            SettingKind::Toggle { get, set } => set(settings, !get(settings)),
+            SettingKind::Dropdown { options, get, set } => {
+                let len = options.len() as isize;
+                set(
+                    settings,
+                    (get(settings) as isize + direction).rem_euclid(len) as usize,
+                );
+            }
+        }
+    }
+
+    /// Sets a slider from a `0..=1` position along its track, snapped to its step.
+    fn set_fraction(&self, settings: &mut GameSettings, fraction: f32) {
+        if let SettingKind::Slider {
+            min,
+            max,
+            step,
+            set,
+            ..
+        } = self.kind
+        {
+            let value = min + (max - min) * fraction.clamp(0.0, 1.0);
+            set(settings, ((value / step).round() * step).clamp(min, max));
+        }
+    }
+
+    fn fraction(&self, settings: &GameSettings) -> f32 {
+        match self.kind {
+            SettingKind::Slider { min, max, get, .. } => (get(settings) - min) / (max - min),
+            _ => 0.0,
+        }
+    }
+}
+
+const RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720),
+    (1600, 900),
+    (1920, 1080),
+    (2560, 1440),
+    (3840, 2160),
+];
+
+const FRAME_LIMITS: [Option<f64>; 6] = [
+    None,
+    Some(30.0),
+    Some(60.0),
+    Some(120.0),
+    Some(144.0),
+    Some(240.0),
+];
+
+const CONTROLS: &[Setting] = &[
+    Setting {
+        label: "Mouse sensitivity",
+        kind: SettingKind::Slider {
+            min: 0.0005,
+            max: 0.01,
+            step: 0.0005,
+            get: |settings| settings.controls.sensitivity,
+            set: |settings, value| settings.controls.sensitivity = value,
+            format: |value| format!("{:.1}", value * 1000.0),
+        },
+    },
+    Setting {
+        label: "Invert Y",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.controls.invert_y,
+            set: |settings, value| settings.controls.invert_y = value,
+        },
+    },
+];
+
+const VIDEO: &[Setting] = &[
+    Setting {
+        label: "Field of view",
+        kind: SettingKind::Slider {
+            min: 30.0,
+            max: 110.0,
+            step: 1.0,
+            get: |settings| settings.video.fov,
+            set: |settings, value| settings.video.fov = value,
+            format: |value| format!("{value:.0}"),
+        },
+    },
+    Setting {
+        label: "Window mode",
+        kind: SettingKind::Dropdown {
+            options: &["Windowed", "Borderless", "Fullscreen"],
+            get: |settings| {
+                WindowModeSetting::ALL
+                    .iter()
+                    .position(|&mode| mode == settings.video.window_mode)
+                    .unwrap_or(0)
+            },
+            set: |settings, index| settings.video.window_mode = WindowModeSetting::ALL[index],
+        },
+    },
+    Setting {
+        label: "Resolution",
+        kind: SettingKind::Dropdown {
+            options: &[
+                "1280x720",
+                "1600x900",
+                "1920x1080",
+                "2560x1440",
+                "3840x2160",
+            ],
+            get: |settings| {
+                RESOLUTIONS
+                    .iter()
+                    .position(|&resolution| resolution == settings.video.resolution)
+                    .unwrap_or(2)
+            },
+            set: |settings, index| settings.video.resolution = RESOLUTIONS[index],
+        },
+    },
+    Setting {
+        label: "VSync",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.video.vsync,
+            set: |settings, value| settings.video.vsync = value,
+        },
+    },
+    Setting {
+        label: "Frame limit",
+        kind: SettingKind::Dropdown {
+            options: &["Off", "30", "60", "120", "144", "240"],
+            get: |settings| {
+                FRAME_LIMITS
+                    .iter()
+                    .position(|&limit| limit == settings.video.frame_limit)
+                    .unwrap_or(0)
+            },
+            set: |settings, index| settings.video.frame_limit = FRAME_LIMITS[index],
+        },
+    },
+    Setting {
+        label: "Anti-aliasing",
+        kind: SettingKind::Dropdown {
+            options: &["Off", "2x MSAA", "4x MSAA", "8x MSAA"],
+            get: |settings| {
+                MsaaSetting::ALL
+                    .iter()
+                    .position(|&msaa| msaa == settings.video.msaa)
+                    .unwrap_or(0)
+            },
+            set: |settings, index| settings.video.msaa = MsaaSetting::ALL[index],
+        },
+    },
+    Setting {
+        label: "Shadows",
+        kind: SettingKind::Dropdown {
+            options: &["Off", "Low", "Medium", "High"],
+            get: |settings| {
+                ShadowQuality::ALL
+                    .iter()
+                    .position(|&quality| quality == settings.video.shadow_quality)
+                    .unwrap_or(0)
+            },
+            set: |settings, index| settings.video.shadow_quality = ShadowQuality::ALL[index],
+        },
+    },
+    Setting {
+        label: "Show FPS",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.video.show_fps,
+            set: |settings, value| settings.video.show_fps = value,
+        },
+    },
+];
+
+const AUDIO: &[Setting] = &[Setting {
+    label: "Master volume",
+    kind: SettingKind::Slider {
+        min: 0.0,
+        max: 1.0,
+        step: 0.05,
+        get: |settings| settings.audio.master_volume,
+        set: |settings, value| settings.audio.master_volume = value,
+        format: |value| format!("{:.0}%", value * 100.0),
+    },
+}];
+
+const GAMEPLAY: &[Setting] = &[
+    Setting {
+        label: "Crosshair size",
+        kind: SettingKind::Slider {
+            min: 0.5,
+            max: 6.0,
+            step: 0.25,
+            get: |settings| settings.gameplay.crosshair_size,
+            set: |settings, value| settings.gameplay.crosshair_size = value,
+            format: |value| format!("{value:.2}"),
+        },
+    },
+    Setting {
+        label: "Camera shake",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.gameplay.camera_shake,
+            set: |settings, value| settings.gameplay.camera_shake = value,
+        },
+    },
+    Setting {
+        label: "Head bob",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.gameplay.headbob,
+            set: |settings, value| settings.gameplay.headbob = value,
+        },
+    },
+    Setting {
+        label: "Landing dip",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.gameplay.landing_dip,
+            set: |settings, value| settings.gameplay.landing_dip = value,
+        },
+    },
+    Setting {
+        label: "Sprint FOV kick",
+        kind: SettingKind::Toggle {
+            get: |settings| settings.gameplay.fov_kick,
+            set: |settings, value| settings.gameplay.fov_kick = value,
+        },
+    },
+];
+
+#[derive(Component, Debug)]
+pub(super) struct SettingsContent;
+
+#[derive(Component, Debug)]
+struct SettingsHint;
+
+#[derive(Component, Debug)]
+struct TabButton(SettingsTab);
+
+#[derive(Component, Debug)]
+pub(super) struct SettingRow(pub usize);
+
+#[derive(Component, Debug)]
+struct SettingValue(usize);
+
+/// Toggles a toggle or opens a dropdown when clicked.
+#[derive(Component, Debug)]
+struct SettingButton(usize);
+
+#[derive(Component, Debug)]
+struct SliderTrack(usize);
+
+#[derive(Component, Debug)]
+struct SliderFill(usize);
+
+#[derive(Component, Debug)]
+struct DropdownOption {
+    row: usize,
+    option: usize,
+}
+
+/// Applies `edit` to a copy of the settings so change detection only fires on real changes.
+fn edit_settings(settings: &mut ResMut<GameSettings>, edit: impl FnOnce(&mut GameSettings)) {
+    let mut edited = settings.clone();
+    edit(&mut edited);
+    settings.set_if_neq(edited);
+}
+
+fn spawn_settings_screen(mut commands: Commands, mut menu: ResMut<SettingsMenu>) {
+    *menu = SettingsMenu::default();
+
+    commands
+        .spawn((
+            Name::new("Settings Screen"),
+            StateScoped(GameState::Settings),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            children![(Text::new("Settings"), TextFont::from_font_size(32.0))],
+        ))
+        .with_children(|screen| {
+            screen
+                .spawn(Node {
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                })
+                .with_children(|tabs| {
+                    for tab in SettingsTab::ALL {
+                        tabs.spawn((
+                            Button,
+                            TabButton(tab),
+                            Node {
+                                padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                                ..default()
+                            },
+                            BackgroundColor(TAB_COLOR),
+                            children![(Text::new(tab.title()), TextFont::from_font_size(20.0))],
+                        ));
+                    }
+                });
+
+            screen.spawn((
+                SettingsContent,
+                Node {
+                    width: Val::Px(ROW_WIDTH),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    padding: UiRect::all(Val::Px(12.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.9)),
+            ));
+
+            screen.spawn((
+                SettingsHint,
+                Text::new(HINT),
+                TextFont::from_font_size(14.0),
+            ));
+        });
+}
+
+/// Respawns the rows when the tab changes or a dropdown opens or closes.
+fn rebuild_settings_content(
+    mut commands: Commands,
+    menu: Res<SettingsMenu>,
+    defaults: Res<DefaultBindings>,
+    content: Single<Entity, With<SettingsContent>>,
+    new_content: Query<(), Added<SettingsContent>>,
+    mut built: Local<Option<(SettingsTab, Option<usize>)>>,
+) {
+    let layout = (menu.tab, menu.dropdown.map(|(row, _)| row));
+    if new_content.is_empty() && *built == Some(layout) {
+        return;
+    }
+    *built = Some(layout);
+
+    commands.entity(*content).despawn_related::<Children>();
+
+    if menu.tab == SettingsTab::Bindings {
+        rebind::spawn_binding_rows(&mut commands, *content, &defaults);
+        return;
+    }
+
+    for (index, setting) in menu.tab.settings().iter().enumerate() {
+        let widget = match setting.kind {
+            SettingKind::Slider { .. } => commands
+                .spawn(Node {
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(12.0),
+                    ..default()
+                })
+                .with_children(|slider| {
+                    slider.spawn((
+                        Button,
+                        SliderTrack(index),
+                        RelativeCursorPosition::default(),
+                        Node {
+                            width: Val::Px(SLIDER_WIDTH),
+                            height: Val::Px(12.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.15, 0.15, 0.18)),
+                        children![(
+                            SliderFill(index),
+                            Node {
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.6, 0.6, 0.75)),
+                        )],
+                    ));
+                    slider.spawn(value_text(index));
+                })
+                .id(),
+            SettingKind::Toggle { .. } | SettingKind::Dropdown { .. } => commands
+                .spawn((
+                    Button,
+                    SettingButton(index),
+                    Node {
+                        min_width: Val::Px(140.0),
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(TAB_COLOR),
+                    children![value_text(index)],
+                ))
+                .id(),
+        };
+
+        if let (SettingKind::Dropdown { options, .. }, Some((row, _))) =
+            (&setting.kind, menu.dropdown)
+        {
+            if row == index {
+                let list = spawn_dropdown_list(&mut commands, index, options);
+                commands.entity(widget).add_child(list);
+            }
+        }
+
+        let row = commands
+            .spawn((
+                SettingRow(index),
+                Node {
+                    justify_content: JustifyContent::SpaceBetween,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(UNFOCUSED_COLOR),
+                children![(Text::new(setting.label), TextFont::from_font_size(18.0))],
+            ))
+            .add_child(widget)
+            .id();
+        commands.entity(*content).add_child(row);
+    }
+}
+
+fn value_text(index: usize) -> impl Bundle {
+    (
+        SettingValue(index),
+        Text::default(),
+        TextFont::from_font_size(16.0),
+    )
+}
+
+fn spawn_dropdown_list(commands: &mut Commands, row: usize, options: &[&str]) -> Entity {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(100.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            GlobalZIndex(1),
+            BackgroundColor(Color::srgb(0.12, 0.12, 0.15)),
+        ))
+        .with_children(|list| {
+            for (option, label) in options.iter().enumerate() {
+                list.spawn((
+                    Button,
+                    DropdownOption { row, option },
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(UNFOCUSED_COLOR),
+                    children![(Text::new(*label), TextFont::from_font_size(16.0))],
+                ));
+            }
+        })
+        .id()
+}
+
+fn update_settings_widgets(
+    menu: Res<SettingsMenu>,
+    settings: Res<GameSettings>,
+    mut tabs: Query<(&TabButton, &mut BackgroundColor)>,
+    mut rows: Query<(&SettingRow, &mut BackgroundColor), Without<TabButton>>,
+    mut options: Query<
+        (&DropdownOption, &mut BackgroundColor),
+        (Without<TabButton>, Without<SettingRow>),
+    >,
+    mut values: Query<(&SettingValue, &mut Text), Without<SettingsHint>>,
+    mut fills: Query<(&SliderFill, &mut Node)>,
+    mut hint: Single<&mut Text, With<SettingsHint>>,
+    new_rows: Query<(), Added<SettingRow>>,
+) {
+    if !menu.is_changed() && !settings.is_changed() && new_rows.is_empty() {
+        return;
+    }
+
+    hint.0 = menu.notice.clone().unwrap_or_else(|| HINT.to_string());
+
+    let entries = menu.tab.settings();
+
+    for (tab, mut color) in tabs.iter_mut() {
+        color.0 = match tab.0 == menu.tab {
+            true => SELECTED_TAB_COLOR,
+            false => TAB_COLOR,
+        };
+    }
+
+    for (row, mut color) in rows.iter_mut() {
+        color.0 = match row.0 == menu.row {
+            true => FOCUSED_COLOR,
+            false => UNFOCUSED_COLOR,
+        };
+    }
+
+    for (option, mut color) in options.iter_mut() {
+        color.0 = match menu.dropdown == Some((option.row, option.option)) {
+            true => FOCUSED_COLOR,
+            false => UNFOCUSED_COLOR,
+        };
+    }
+
+    for (value, mut text) in values.iter_mut() {
+        if let Some(setting) = entries.get(value.0) {
+            text.0 = setting.display(&settings);
+        }
+    }
+
+    for (fill, mut node) in fills.iter_mut() {
+        if let Some(setting) = entries.get(fill.0) {
+            node.width = Val::Percent(setting.fraction(&settings) * 100.0);
+        }
+    }
+}
+
+fn handle_settings_mouse(
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+    tabs: Query<(&Interaction, &TabButton), Changed<Interaction>>,
+    buttons: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    options: Query<(&Interaction, &DropdownOption), Changed<Interaction>>,
+    tracks: Query<(&Interaction, &SliderTrack, &RelativeCursorPosition)>,
+) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    let entries = menu.tab.settings();
+
+    for (interaction, tab) in tabs.iter() {
+        if *interaction == Interaction::Pressed && tab.0 != menu.tab {
+            *menu = SettingsMenu {
+                tab: tab.0,
+                ..default()
+            };
+            return;
+        }
+    }
+
+    for (interaction, option) in options.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(SettingKind::Dropdown { set, .. }) =
+            entries.get(option.row).map(|setting| &setting.kind)
+        {
+            edit_settings(&mut settings, |settings| set(settings, option.option));
+        }
+        menu.dropdown = None;
+        return;
+    }
+
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        menu.row = button.0;
+        activate(&mut menu, &mut settings);
+    }
+
+    // Sliders follow the cursor for as long as the track is held.
+    for (interaction, track, cursor) in tracks.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let (Some(setting), Some(position)) = (entries.get(track.0), cursor.normalized) else {
+            continue;
+        };
+
+        menu.row = track.0;
+        edit_settings(&mut settings, |settings| {
+            setting.set_fraction(settings, position.x)
+        });
+    }
+}
+
+/// Confirming flips toggles and opens or commits dropdowns, sliders only react to left/right.
+fn activate(menu: &mut SettingsMenu, settings: &mut ResMut<GameSettings>) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    let Some(setting) = menu.focused() else {
+        return;
+    };
+
+    match (&setting.kind, menu.dropdown) {
+        (SettingKind::Toggle { .. }, _) => {
+            edit_settings(settings, |settings| setting.adjust(settings, 1));
+        }
+        (SettingKind::Dropdown { set, .. }, Some((row, option))) if row == menu.row => {
+            edit_settings(settings, |settings| set(settings, option));
+            menu.dropdown = None;
+        }
+        (SettingKind::Dropdown { get, .. }, _) => {
+            menu.dropdown = Some((menu.row, get(settings)));
+        }
+        (SettingKind::Slider { .. }, _) => {}
+    }
+}
+
+fn move_focus(menu: &mut SettingsMenu, defaults: &DefaultBindings, direction: isize) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    if let Some((row, option)) = menu.dropdown {
+        if let Some(SettingKind::Dropdown { options, .. }) =
+            menu.tab.settings().get(row).map(|setting| &setting.kind)
+        {
+            let option = (option as isize + direction).rem_euclid(options.len() as isize) as usize;
+            menu.dropdown = Some((row, option));
+        }
+        return;
+    }
+
+    let last = menu.row_count(defaults).saturating_sub(1);
+    menu.row = menu.row.saturating_add_signed(direction).min(last);
+}
+
+fn focus_previous(
+    _trigger: Trigger<Started<MenuUp>>,
+    mut menu: ResMut<SettingsMenu>,
+    defaults: Res<DefaultBindings>,
+) {
+    move_focus(&mut menu, &defaults, -1);
+}
+
+fn focus_next(
+    _trigger: Trigger<Started<MenuDown>>,
+    mut menu: ResMut<SettingsMenu>,
+    defaults: Res<DefaultBindings>,
+) {
+    move_focus(&mut menu, &defaults, 1);
+}
+
+fn adjust_focused(menu: &mut SettingsMenu, settings: &mut ResMut<GameSettings>, direction: isize) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    menu.dropdown = None;
+    if let Some(setting) = menu.focused() {
+        edit_settings(settings, |settings| setting.adjust(settings, direction));
+    }
+}
+
+fn decrease_setting(
+    _trigger: Trigger<Started<MenuLeft>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+) {
+    adjust_focused(&mut menu, &mut settings, -1);
+}
+
+fn increase_setting(
+    _trigger: Trigger<Started<MenuRight>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+) {
+    adjust_focused(&mut menu, &mut settings, 1);
+}
+
+fn confirm_setting(
+    _trigger: Trigger<Started<MenuConfirm>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+) {
+    activate(&mut menu, &mut settings);
+}
+
+fn next_tab(_trigger: Trigger<Started<NextTab>>, mut menu: ResMut<SettingsMenu>) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    *menu = SettingsMenu {
+        tab: menu.tab.cycle(1),
+        ..default()
+    };
+}
+
+fn previous_tab(_trigger: Trigger<Started<PreviousTab>>, mut menu: ResMut<SettingsMenu>) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    *menu = SettingsMenu {
+        tab: menu.tab.cycle(-1),
+        ..default()
+    };
+}
@@ -0,0 +1,35 @@
+pub(crate) mod bindings;
+pub(crate) mod config;
+mod menu;
+mod rebind;
+mod video;
+
+use crate::GameState;
+use crate::gameplay::input::CloseSettings;
+use crate::gameplay::settings::menu::SettingsMenu;
+use crate::gameplay::transition::RequestGameState;
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(config::plugin)
+        .add_plugins(bindings::plugin)
+        .add_plugins(menu::plugin)
+        .add_plugins(rebind::plugin)
+        .add_plugins(video::plugin);
+
+    app.add_observer(close_settings);
+}
+
+fn close_settings(
+    _trigger: Trigger<Fired<CloseSettings>>,
+    mut commands: Commands,
+    menu: Res<SettingsMenu>,
+) {
+    // Escape cancels a key capture instead of closing the menu.
+    if menu.is_capturing() {
+        return;
+    }
+
+    commands.trigger(RequestGameState(GameState::Paused));
+}
@@ -0,0 +1,308 @@
+use crate::gameplay::input::{MenuConfirm, MenuLeft, MenuRight, button_label, key_label};
+use crate::gameplay::settings::bindings::{
+    BindingDevice, CapturedInput, DefaultBindings, Rebound, ReservedInputs, lookup, rebind,
+};
+use crate::gameplay::settings::config::GameSettings;
+use crate::gameplay::settings::menu::{
+    FOCUSED_COLOR, SettingRow, SettingsMenu, SettingsTab, TAB_COLOR, UNFOCUSED_COLOR,
+};
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+const COLUMN_WIDTH: f32 = 140.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Capture>();
+
+    app.add_observer(previous_column)
+        .add_observer(next_column)
+        .add_observer(confirm_binding);
+}
+
+/// Binding row and device column waiting for the next input.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Capture {
+    row: usize,
+    device: BindingDevice,
+    /// Set one frame after the capture starts, so the press that started it isn't captured.
+    armed: bool,
+}
+
+#[derive(Component, Debug)]
+struct BindingButton {
+    row: usize,
+    device: BindingDevice,
+}
+
+#[derive(Component, Debug)]
+struct BindingValue {
+    row: usize,
+    device: BindingDevice,
+}
+
+#[derive(Component, Debug)]
+struct ResetBindingsButton;
+
+/// One row per rebindable action grouped by context, followed by the reset button.
+pub(super) fn spawn_binding_rows(
+    commands: &mut Commands,
+    content: Entity,
+    defaults: &DefaultBindings,
+) {
+    let mut context = None;
+
+    for (row, default) in defaults.0.iter().enumerate() {
+        if context != Some(default.context) {
+            context = Some(default.context);
+            commands.entity(content).with_child((
+                Text::new(default.context.title()),
+                TextFont::from_font_size(14.0),
+                TextColor(Color::srgb(0.6, 0.6, 0.7)),
+            ));
+        }
+
+        commands.entity(content).with_children(|content| {
+            content
+                .spawn((
+                    SettingRow(row),
+                    Node {
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(UNFOCUSED_COLOR),
+                    children![(Text::new(default.label), TextFont::from_font_size(16.0))],
+                ))
+                .with_children(|columns| {
+                    columns
+                        .spawn(Node {
+                            column_gap: Val::Px(8.0),
+                            ..default()
+                        })
+                        .with_children(|columns| {
+                            for device in [BindingDevice::Keyboard, BindingDevice::Gamepad] {
+                                columns.spawn(binding_button(row, device));
+                            }
+                        });
+                });
+        });
+    }
+
+    commands.entity(content).with_child((
+        SettingRow(defaults.0.len()),
+        Node {
+            justify_content: JustifyContent::Center,
+            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+            ..default()
+        },
+        BackgroundColor(UNFOCUSED_COLOR),
+        children![(
+            Button,
+            ResetBindingsButton,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(TAB_COLOR),
+            children![(
+                Text::new("Reset to defaults"),
+                TextFont::from_font_size(16.0)
+            )],
+        )],
+    ));
+}
+
+fn binding_button(row: usize, device: BindingDevice) -> impl Bundle {
+    (
+        Button,
+        BindingButton { row, device },
+        Node {
+            width: Val::Px(COLUMN_WIDTH),
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(TAB_COLOR),
+        children![(
+            BindingValue { row, device },
+            Text::default(),
+            TextFont::from_font_size(14.0),
+        )],
+    )
+}
+
+fn binding_text(menu: &SettingsMenu, binding: &BindingValue, input: Option<String>) -> String {
+    let capturing = menu
+        .capture
+        .is_some_and(|capture| capture.row == binding.row && capture.device == binding.device);
+
+    match (capturing, binding.device) {
+        (true, BindingDevice::Keyboard) => "Press a key...".to_string(),
+        (true, BindingDevice::Gamepad) => "Press a button...".to_string(),
+        (false, _) => input.unwrap_or_else(|| "-".to_string()),
+    }
+}
+
+pub(super) fn update_binding_widgets(
+    menu: Res<SettingsMenu>,
+    settings: Res<GameSettings>,
+    defaults: Res<DefaultBindings>,
+    mut values: Query<(&BindingValue, &mut Text)>,
+    mut buttons: Query<(&BindingButton, &mut BackgroundColor)>,
+    new_values: Query<(), Added<BindingValue>>,
+) {
+    if menu.tab != SettingsTab::Bindings {
+        return;
+    }
+    if !menu.is_changed() && !settings.is_changed() && new_values.is_empty() {
+        return;
+    }
+
+    for (value, mut text) in values.iter_mut() {
+        let Some(default) = defaults.0.get(value.row) else {
+            continue;
+        };
+
+        let binding = lookup(&defaults, &settings, default.id);
+        let input = match value.device {
+            BindingDevice::Keyboard => binding.keyboard.map(key_label),
+            BindingDevice::Gamepad => binding.gamepad.map(button_label),
+        };
+        text.0 = binding_text(&menu, value, input);
+    }
+
+    for (button, mut color) in buttons.iter_mut() {
+        color.0 = match button.row == menu.row && button.device == menu.column {
+            true => FOCUSED_COLOR,
+            false => TAB_COLOR,
+        };
+    }
+}
+
+fn start_capture(menu: &mut SettingsMenu, row: usize, device: BindingDevice) {
+    menu.row = row;
+    menu.column = device;
+    menu.notice = None;
+    menu.capture = Some(Capture {
+        row,
+        device,
+        armed: false,
+    });
+}
+
+fn reset_bindings(menu: &mut SettingsMenu, settings: &mut GameSettings) {
+    settings.controls.bindings.clear();
+    menu.notice = Some("Bindings reset to defaults".to_string());
+}
+
+pub(super) fn handle_binding_mouse(
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+    defaults: Res<DefaultBindings>,
+    buttons: Query<(&Interaction, &BindingButton), Changed<Interaction>>,
+    reset: Query<&Interaction, (Changed<Interaction>, With<ResetBindingsButton>)>,
+) {
+    if menu.is_capturing() {
+        return;
+    }
+
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            start_capture(&mut menu, button.row, button.device);
+            return;
+        }
+    }
+
+    if reset
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        menu.row = defaults.0.len();
+        reset_bindings(&mut menu, &mut settings);
+    }
+}
+
+/// Binds the next key or gamepad button pressed, Escape cancels.
+pub(super) fn capture_binding(
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+    defaults: Res<DefaultBindings>,
+    reserved: Res<ReservedInputs>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+) {
+    let Some(mut capture) = menu.capture else {
+        return;
+    };
+    if !capture.armed {
+        capture.armed = true;
+        menu.capture = Some(capture);
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Escape) {
+        menu.capture = None;
+        return;
+    }
+
+    let input = match capture.device {
+        BindingDevice::Keyboard => keys
+            .get_just_pressed()
+            .next()
+            .copied()
+            .map(CapturedInput::Key),
+        BindingDevice::Gamepad => gamepads
+            .iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+            .map(CapturedInput::Button),
+    };
+    let Some(input) = input else {
+        return;
+    };
+
+    menu.capture = None;
+    menu.notice = match rebind(&defaults, &reserved, &mut settings, capture.row, input) {
+        Some(Rebound::Swapped(label)) => Some(format!("Swapped with {label}")),
+        Some(Rebound::Reserved(label)) => Some(format!("Already used by {label}")),
+        Some(Rebound::Bound) | None => None,
+    };
+}
+
+fn change_column(menu: &mut SettingsMenu) {
+    if menu.tab != SettingsTab::Bindings || menu.is_capturing() {
+        return;
+    }
+
+    menu.column = match menu.column {
+        BindingDevice::Keyboard => BindingDevice::Gamepad,
+        BindingDevice::Gamepad => BindingDevice::Keyboard,
+    };
+}
+
+fn previous_column(_trigger: Trigger<Started<MenuLeft>>, mut menu: ResMut<SettingsMenu>) {
+    change_column(&mut menu);
+}
+
+fn next_column(_trigger: Trigger<Started<MenuRight>>, mut menu: ResMut<SettingsMenu>) {
+    change_column(&mut menu);
+}
+
+fn confirm_binding(
+    _trigger: Trigger<Started<MenuConfirm>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut settings: ResMut<GameSettings>,
+    defaults: Res<DefaultBindings>,
+) {
+    if menu.tab != SettingsTab::Bindings || menu.is_capturing() {
+        return;
+    }
+
+    match menu.row < defaults.0.len() {
+        true => {
+            let (row, device) = (menu.row, menu.column);
+            start_capture(&mut menu, row, device);
+        }
+        false => reset_bindings(&mut menu, &mut settings),
+    }
+}
@@ -0,0 +1,121 @@
+use crate::gameplay::settings::config::{
+    GameSettings, MsaaSetting, ShadowQuality, WindowModeSetting,
+};
+use bevy::dev_tools::fps_overlay::FpsOverlayConfig;
+use bevy::pbr::{CascadeShadowConfig, CascadeShadowConfigBuilder, DirectionalLightShadowMap};
+use bevy::prelude::*;
+use bevy::window::{MonitorSelection, PresentMode, PrimaryWindow, VideoModeSelection, WindowMode};
+use bevy_framepace::{FramepaceSettings, Limiter};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (apply_window_settings, apply_frame_limit, apply_fps_overlay)
+            .run_if(resource_changed::<GameSettings>),
+    );
+
+    app.add_systems(Update, (apply_msaa, apply_shadow_quality));
+}
+
+fn apply_window_settings(
+    settings: Res<GameSettings>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    let video = &settings.video;
+
+    let mode = match video.window_mode {
+        WindowModeSetting::Windowed => WindowMode::Windowed,
+        WindowModeSetting::BorderlessFullscreen => {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        }
+        WindowModeSetting::Fullscreen => {
+            WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+        }
+    };
+    if window.mode != mode {
+        window.mode = mode;
+    }
+
+    let (width, height) = video.resolution;
+    let current = (
+        window.resolution.physical_width(),
+        window.resolution.physical_height(),
+    );
+    if mode == WindowMode::Windowed && current != video.resolution {
+        window.resolution.set_physical_resolution(width, height);
+    }
+
+    let present_mode = match video.vsync {
+        true => PresentMode::AutoVsync,
+        false => PresentMode::AutoNoVsync,
+    };
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+fn apply_frame_limit(settings: Res<GameSettings>, mut framepace: ResMut<FramepaceSettings>) {
+    framepace.limiter = match settings.video.frame_limit {
+        Some(fps) => Limiter::from_framerate(fps),
+        None => Limiter::Off,
+    };
+}
+
+/// Also runs for cameras spawned after the settings last changed, e.g. the player's.
+fn apply_msaa(
+    settings: Res<GameSettings>,
+    mut cameras: Query<&mut Msaa, With<Camera3d>>,
+    new_cameras: Query<(), Added<Camera3d>>,
+) {
+    if !settings.is_changed() && new_cameras.is_empty() {
+        return;
+    }
+
+    let msaa = match settings.video.msaa {
+        MsaaSetting::Off => Msaa::Off,
+        MsaaSetting::Sample2 => Msaa::Sample2,
+        MsaaSetting::Sample4 => Msaa::Sample4,
+        MsaaSetting::Sample8 => Msaa::Sample8,
+    };
+
+    for mut camera_msaa in cameras.iter_mut() {
+        camera_msaa.set_if_neq(msaa);
+    }
+}
+
+fn apply_fps_overlay(settings: Res<GameSettings>, mut overlay: ResMut<FpsOverlayConfig>) {
+    overlay.enabled = settings.video.show_fps;
+}
+
+/// Also runs for lights spawned after the settings last changed, e.g. by a level load.
+fn apply_shadow_quality(
+    settings: Res<GameSettings>,
+    mut shadow_map: ResMut<DirectionalLightShadowMap>,
+    mut lights: Query<(&mut DirectionalLight, &mut CascadeShadowConfig)>,
+    new_lights: Query<(), Added<DirectionalLight>>,
+) {
+    if !settings.is_changed() && new_lights.is_empty() {
+        return;
+    }
+
+    let (size, num_cascades, maximum_distance) = match settings.video.shadow_quality {
+        ShadowQuality::Off | ShadowQuality::Low => (1024, 1, 20.0),
+        ShadowQuality::Medium => (2048, 2, 50.0),
+        ShadowQuality::High => (4096, 4, 100.0),
+    };
+
+    shadow_map.size = size;
+
+    let cascades = CascadeShadowConfigBuilder {
+        num_cascades,
+        maximum_distance,
+        first_cascade_far_bound: maximum_distance / 10.0,
+        ..default()
+    }
+    .build();
+
+    for (mut light, mut config) in lights.iter_mut() {
+        light.shadows_enabled = settings.video.shadow_quality != ShadowQuality::Off;
+        *config = cascades.clone();
+    }
+}
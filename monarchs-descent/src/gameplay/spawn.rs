@@ -0,0 +1,278 @@
+use crate::gameplay::player::Player;
+use crate::gameplay::streaming::StreamedCells;
+use avian3d::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::cmp::Reverse;
+
+/// Used when a level has no spawn point for the team, high enough to drop onto the map.
+const FALLBACK_SPAWN: Vec3 = Vec3::new(0.0, 15.0, 0.0);
+/// Height of the player's capsule centre above the spawn point.
+const SPAWN_HEIGHT: f32 = 1.1;
+/// Radius around the capsule centre that has to be clear for a spawn point to be used.
+const OCCUPIED_RADIUS: f32 = 0.6;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SpawnPoint>()
+        .register_type::<Team>()
+        .register_type::<KillPlane>()
+        .register_type::<AwaitingGround>();
+
+    app.init_resource::<KillPlane>();
+
+    app.add_observer(respawn_player);
+
+    app.add_systems(Update, (apply_kill_plane, release_awaiting_ground));
+}
+
+/// Where players of `team` can spawn, usually placed in Blender through glTF extras, e.g.
+/// `SpawnPoint` = `(priority: 10)`.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+#[reflect(Component, Default)]
+pub struct SpawnPoint {
+    #[reflect(default)]
+    pub team: u8,
+    /// Free points with a higher priority are picked first.
+    #[reflect(default)]
+    pub priority: i32,
+}
+
+/// Team an actor belongs to, selects which spawn points it can use.
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct Team(pub u8);
+
+/// Players falling below this height die, anything else is despawned.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct KillPlane {
+    pub height: f32,
+}
+
+impl Default for KillPlane {
+    fn default() -> Self {
+        Self { height: -50.0 }
+    }
+}
+
+/// Triggered on an entity when it dies, players are respawned at a spawn point.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Died;
+
+/// The body is disabled after respawning until the streamed cell under it has loaded.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[component(storage = "SparseSet")]
+struct AwaitingGround;
+
+#[derive(SystemParam)]
+pub(super) struct SpawnPoints<'w, 's> {
+    points: Query<'w, 's, (&'static SpawnPoint, &'static GlobalTransform)>,
+    sensors: Query<'w, 's, (), With<Sensor>>,
+    spatial_query: SpatialQuery<'w, 's>,
+}
+
+impl SpawnPoints<'_, '_> {
+    /// Transform to place an actor of `team` at, the highest priority free spawn point. `actor`
+    /// doesn't count as occupying a point.
+    pub(super) fn choose(&self, team: Team, actor: Option<Entity>) -> Transform {
+        let filter = SpatialQueryFilter::from_excluded_entities(actor);
+
+        let candidates = candidates(self.points.iter(), team);
+
+        // Trigger volumes and level exits don't block a spawn point, only solid colliders do.
+        let free = candidates.iter().find(|transform| {
+            self.spatial_query
+                .shape_intersections(
+                    &Collider::sphere(OCCUPIED_RADIUS),
+                    spawn_position(transform),
+                    Quat::IDENTITY,
+                    &filter,
+                )
+                .iter()
+                .all(|entity| self.sensors.contains(*entity))
+        });
+
+        let chosen = match (free, candidates.first()) {
+            (Some(transform), _) => transform,
+            (None, Some(transform)) => {
+                warn!(
+                    ?team,
+                    "every spawn point is occupied, using the highest priority"
+                );
+                transform
+            }
+            (None, None) => {
+                warn!(?team, "no spawn point for team, using the fallback");
+                return Transform::from_translation(FALLBACK_SPAWN);
+            }
+        };
+
+        // Only the heading is kept, the player always spawns upright.
+        let (yaw, _, _) = chosen.rotation.to_euler(EulerRot::YXZ);
+        Transform::from_translation(spawn_position(chosen))
+            .with_rotation(Quat::from_rotation_y(yaw))
+    }
+}
+
+/// Transforms of the spawn points for `team`, highest priority first. Points with the same
+/// priority keep their query order.
+fn candidates<'a>(
+    points: impl IntoIterator<Item = (&'a SpawnPoint, &'a GlobalTransform)>,
+    team: Team,
+) -> Vec<Transform> {
+    let mut candidates: Vec<_> = points
+        .into_iter()
+        .filter(|(point, _)| point.team == team.0)
+        .map(|(point, transform)| (point.priority, transform.compute_transform()))
+        .collect();
+    candidates.sort_by_key(|(priority, _)| Reverse(*priority));
+    candidates
+        .into_iter()
+        .map(|(_, transform)| transform)
+        .collect()
+}
+
+fn spawn_position(point: &Transform) -> Vec3 {
+    point.translation + Vec3::Y * SPAWN_HEIGHT
+}
+
+fn apply_kill_plane(
+    mut commands: Commands,
+    kill_plane: Res<KillPlane>,
+    bodies: Query<(Entity, &RigidBody, &GlobalTransform), Without<RigidBodyDisabled>>,
+    players: Query<(), With<Player>>,
+) {
+    for (entity, body, transform) in bodies.iter() {
+        // Static map geometry and terrain can reach below the plane, only falling bodies die.
+        if *body != RigidBody::Dynamic || transform.translation().y >= kill_plane.height {
+            continue;
+        }
+
+        debug!(?entity, "fell below the kill plane");
+        match players.contains(entity) {
+            true => commands.trigger_targets(Died, entity),
+            false => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+fn respawn_player(
+    trigger: Trigger<Died>,
+    mut commands: Commands,
+    spawn_points: SpawnPoints,
+    cells: StreamedCells,
+    mut players: Query<
+        (
+            &Team,
+            &mut Transform,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        With<Player>,
+    >,
+) {
+    let player = trigger.target();
+    let Ok((team, mut transform, mut linear, mut angular)) = players.get_mut(player) else {
+        return;
+    };
+
+    *transform = spawn_points.choose(*team, Some(player));
+    *linear = LinearVelocity::ZERO;
+    *angular = AngularVelocity::ZERO;
+
+    if !cells.loaded_at(transform.translation) {
+        debug!(
+            ?player,
+            "spawn point is still streaming in, holding the player in place"
+        );
+        commands
+            .entity(player)
+            .insert((AwaitingGround, RigidBodyDisabled));
+    }
+
+    info!(?player, translation = ?transform.translation, "respawned player");
+}
+
+fn release_awaiting_ground(
+    mut commands: Commands,
+    cells: StreamedCells,
+    players: Query<(Entity, &GlobalTransform), With<AwaitingGround>>,
+) {
+    for (player, transform) in players.iter() {
+        if cells.loaded_at(transform.translation()) {
+            commands
+                .entity(player)
+                .remove::<(AwaitingGround, RigidBodyDisabled)>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn point(team: u8, priority: i32, x: f32) -> (SpawnPoint, GlobalTransform) {
+        (
+            SpawnPoint { team, priority },
+            GlobalTransform::from_translation(Vec3::X * x),
+        )
+    }
+
+    fn order(points: &[(SpawnPoint, GlobalTransform)], team: Team) -> Vec<f32> {
+        candidates(
+            points.iter().map(|(point, transform)| (point, transform)),
+            team,
+        )
+        .iter()
+        .map(|transform| transform.translation.x)
+        .collect()
+    }
+
+    #[test]
+    fn highest_priority_first() {
+        let points = [point(0, 1, 1.0), point(0, 10, 2.0), point(0, -5, 3.0)];
+        assert_eq!(order(&points, Team(0)), [2.0, 1.0, 3.0]);
+    }
+
+    #[test]
+    fn equal_priorities_keep_their_order() {
+        let points = [point(0, 0, 1.0), point(0, 0, 2.0), point(0, 0, 3.0)];
+        assert_eq!(order(&points, Team(0)), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn extreme_priorities_do_not_overflow() {
+        let points = [point(0, i32::MIN, 1.0), point(0, i32::MAX, 2.0)];
+        assert_eq!(order(&points, Team(0)), [2.0, 1.0]);
+    }
+
+    #[test]
+    fn other_teams_are_skipped() {
+        let points = [point(1, 10, 1.0), point(0, 0, 2.0)];
+        assert_eq!(order(&points, Team(0)), [2.0]);
+        assert!(order(&points, Team(2)).is_empty());
+    }
+
+    #[test]
+    fn kill_plane_only_despawns_dynamic_bodies() {
+        let mut world = World::new();
+        world.init_resource::<KillPlane>();
+        let below = GlobalTransform::from_translation(Vec3::NEG_Y * 100.0);
+
+        let ground = world.spawn((RigidBody::Static, below)).id();
+        let platform = world.spawn((RigidBody::Kinematic, below)).id();
+        let prop = world.spawn((RigidBody::Dynamic, below)).id();
+        let resting = world
+            .spawn((RigidBody::Dynamic, GlobalTransform::IDENTITY))
+            .id();
+
+        world.run_system_once(apply_kill_plane).unwrap();
+
+        assert!(world.get_entity(ground).is_ok());
+        assert!(world.get_entity(platform).is_ok());
+        assert!(world.get_entity(prop).is_err());
+        assert!(world.get_entity(resting).is_ok());
+    }
+}
@@ -0,0 +1,389 @@
+//! Splits large maps into square cells that are loaded as the player comes near and unloaded
+//! once they are left behind.
+//!
+//! A [`Streaming`] root decides which cells exist, content is added to them by observers of
+//! [`Cell`] being added: a [`Terrain`] chunk, a scene from [`CellScenes`], or both. Slow work
+//! happens off the main thread or in the asset server, with [`CellContentLoading`] on the cell or
+//! its children until it is done. Everything parented to a cell is despawned with it.
+//!
+//! [`Terrain`]: crate::gameplay::terrain::Terrain
+
+use crate::gameplay::player::Player;
+use crate::gameplay::spawn::SpawnPoint;
+use avian3d::prelude::ColliderConstructor::TrimeshFromMesh;
+use avian3d::prelude::{
+    ColliderConstructorHierarchy, ColliderConstructorHierarchyReady, RigidBody,
+};
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Streaming>()
+        .register_type::<CellScenes>()
+        .register_type::<LoadedCells>()
+        .register_type::<Cell>()
+        .register_type::<CellContentLoading>()
+        .register_type::<StreamingDebug>();
+
+    app.init_resource::<StreamingDebug>();
+
+    app.add_observer(load_cell_scene)
+        .add_observer(finish_cell_scene)
+        .add_observer(reload_cells);
+
+    app.add_systems(
+        Update,
+        (
+            reload_changed_streaming,
+            stream_cells,
+            fail_cell_scenes,
+            finish_loading_cells,
+            draw_cells.run_if(|debug: Res<StreamingDebug>| debug.show_cells),
+        )
+            .chain(),
+    );
+}
+
+/// Streams cells of the world around the entity, e.g. placed in Blender through glTF extras as
+/// `Streaming` = `(cell_size: 128.0)`.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+#[require(Transform, Visibility, LoadedCells)]
+pub struct Streaming {
+    /// Width of a cell in world units.
+    #[reflect(default = "defaults::cell_size")]
+    pub cell_size: f32,
+    /// Cells with their centre this close to the player are loaded.
+    #[reflect(default = "defaults::load_distance")]
+    pub load_distance: f32,
+    /// Loaded cells are kept until their centre is this far away, more than `load_distance` so
+    /// walking along a cell border doesn't load and unload it over and over.
+    #[reflect(default = "defaults::unload_distance")]
+    pub unload_distance: f32,
+    /// Cells started per frame, nearest first, so crossing into new cells spreads its work out.
+    #[reflect(default = "defaults::loads_per_frame")]
+    pub loads_per_frame: usize,
+}
+
+mod defaults {
+    pub(super) fn cell_size() -> f32 {
+        64.0
+    }
+
+    pub(super) fn load_distance() -> f32 {
+        256.0
+    }
+
+    pub(super) fn unload_distance() -> f32 {
+        320.0
+    }
+
+    pub(super) fn loads_per_frame() -> usize {
+        2
+    }
+}
+
+impl Default for Streaming {
+    fn default() -> Self {
+        Self {
+            cell_size: defaults::cell_size(),
+            load_distance: defaults::load_distance(),
+            unload_distance: defaults::unload_distance(),
+            loads_per_frame: defaults::loads_per_frame(),
+        }
+    }
+}
+
+impl Streaming {
+    /// Offset of a cell's centre from the root.
+    pub fn cell_translation(&self, coord: IVec2) -> Vec3 {
+        let centre = coord.as_vec2() * self.cell_size;
+        Vec3::new(centre.x, 0.0, centre.y)
+    }
+
+    fn cell_at(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).round().as_ivec2()
+    }
+}
+
+/// glTF file spawned in every cell of a [`Streaming`] root, with `{x}` and `{z}` replaced by the
+/// cell's coordinates, e.g. `maps/overworld/cell_{x}_{z}.glb`.
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct CellScenes {
+    pub path: String,
+}
+
+impl CellScenes {
+    fn path(&self, coord: IVec2) -> String {
+        self.path
+            .replace("{x}", &coord.x.to_string())
+            .replace("{z}", &coord.y.to_string())
+    }
+}
+
+/// Cells of a [`Streaming`] root that are loading or loaded.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct LoadedCells(HashMap<IVec2, Entity>);
+
+/// A square of the world belonging to the [`Streaming`] root it is a child of.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+#[require(Transform, Visibility)]
+pub struct Cell {
+    pub coord: IVec2,
+    /// Set once neither the cell nor its children have [`CellContentLoading`].
+    pub loaded: bool,
+}
+
+/// Keeps the cell it is on, or the cell it is a child of, loading until removed.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct CellContentLoading;
+
+/// Triggered on a [`Streaming`] root to unload all its cells, the ones still in range are loaded
+/// again.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ReloadCells;
+
+/// Debug overlay for the editor.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct StreamingDebug {
+    /// Outlines every cell, green once loaded and yellow while loading.
+    pub show_cells: bool,
+}
+
+/// Scene spawned into a cell from [`CellScenes`].
+#[derive(Component, Debug)]
+struct CellScene;
+
+/// Checks whether the streamed cells under a point have loaded, so anything spawned there has
+/// ground to land on.
+#[derive(SystemParam)]
+pub struct StreamedCells<'w, 's> {
+    roots: Query<
+        'w,
+        's,
+        (
+            &'static Streaming,
+            &'static GlobalTransform,
+            &'static LoadedCells,
+        ),
+    >,
+    cells: Query<'w, 's, &'static Cell>,
+}
+
+impl StreamedCells<'_, '_> {
+    /// True once the cell containing `position` is loaded in every [`Streaming`] root, or if
+    /// there are none.
+    pub fn loaded_at(&self, position: Vec3) -> bool {
+        self.roots.iter().all(|(streaming, transform, loaded)| {
+            let local = transform.affine().inverse().transform_point3(position).xz();
+            loaded
+                .0
+                .get(&streaming.cell_at(local))
+                .and_then(|&cell| self.cells.get(cell).ok())
+                .is_some_and(|cell| cell.loaded)
+        })
+    }
+}
+
+fn reload_changed_streaming(mut commands: Commands, roots: Query<(Entity, Ref<Streaming>)>) {
+    for (root, streaming) in roots.iter() {
+        if streaming.is_changed() && !streaming.is_added() {
+            commands.trigger_targets(ReloadCells, root);
+        }
+    }
+}
+
+fn reload_cells(
+    trigger: Trigger<ReloadCells>,
+    mut commands: Commands,
+    mut roots: Query<&mut LoadedCells>,
+) {
+    let Ok(mut cells) = roots.get_mut(trigger.target()) else {
+        return;
+    };
+
+    for (_, cell) in cells.0.drain() {
+        commands.entity(cell).despawn();
+    }
+}
+
+/// Without a player, cells around the spawn points are loaded so there is ground to spawn on.
+fn stream_cells(
+    mut commands: Commands,
+    mut roots: Query<(Entity, &Streaming, &GlobalTransform, &mut LoadedCells)>,
+    players: Query<&GlobalTransform, With<Player>>,
+    spawn_points: Query<&GlobalTransform, With<SpawnPoint>>,
+) {
+    let focus: Vec<Vec3> = match players.is_empty() {
+        true => spawn_points
+            .iter()
+            .map(|point| point.translation())
+            .collect(),
+        false => players.iter().map(|player| player.translation()).collect(),
+    };
+
+    for (root, streaming, transform, mut cells) in roots.iter_mut() {
+        let local = transform.affine().inverse();
+        let focus: Vec<Vec2> = focus
+            .iter()
+            .map(|point| local.transform_point3(*point).xz())
+            .collect();
+        let distance = |coord: IVec2| {
+            let centre = streaming.cell_translation(coord).xz();
+            focus
+                .iter()
+                .map(|point| point.distance(centre))
+                .fold(f32::INFINITY, f32::min)
+        };
+
+        cells.0.retain(|&coord, &mut cell| {
+            let keep = distance(coord) <= streaming.unload_distance;
+            if !keep {
+                debug!(?root, ?coord, "unloading cell");
+                commands.entity(cell).despawn();
+            }
+            keep
+        });
+
+        let reach = (streaming.load_distance / streaming.cell_size).ceil() as i32;
+        let nearby: HashSet<IVec2> = focus
+            .iter()
+            .flat_map(|point| {
+                let centre = streaming.cell_at(*point);
+                (-reach..=reach)
+                    .flat_map(move |z| (-reach..=reach).map(move |x| centre + IVec2::new(x, z)))
+            })
+            .filter(|coord| !cells.0.contains_key(coord))
+            .collect();
+        let mut wanted: Vec<(IVec2, f32)> = nearby
+            .into_iter()
+            .map(|coord| (coord, distance(coord)))
+            .filter(|(_, distance)| *distance <= streaming.load_distance)
+            .collect();
+        wanted.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        for (coord, _) in wanted.into_iter().take(streaming.loads_per_frame) {
+            debug!(?root, ?coord, "loading cell");
+            let cell = commands
+                .spawn((
+                    Name::new(format!("Cell {} {}", coord.x, coord.y)),
+                    ChildOf(root),
+                    Cell {
+                        coord,
+                        loaded: false,
+                    },
+                    Transform::from_translation(streaming.cell_translation(coord)),
+                ))
+                .id();
+            cells.0.insert(coord, cell);
+        }
+    }
+}
+
+fn load_cell_scene(
+    trigger: Trigger<OnAdd, Cell>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    cells: Query<(&Cell, &ChildOf)>,
+    roots: Query<&CellScenes>,
+) {
+    let cell = trigger.target();
+    let Ok((Cell { coord, .. }, child_of)) = cells.get(cell) else {
+        return;
+    };
+    let Ok(scenes) = roots.get(child_of.parent()) else {
+        return;
+    };
+
+    let path = scenes.path(*coord);
+    commands.spawn((
+        Name::new(path.clone()),
+        ChildOf(cell),
+        CellScene,
+        CellContentLoading,
+        SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(path))),
+        ColliderConstructorHierarchy::new(TrimeshFromMesh),
+        RigidBody::Static,
+    ));
+}
+
+/// Waits for the scene's colliders rather than the scene itself, so a loaded cell has ground.
+fn finish_cell_scene(
+    trigger: Trigger<ColliderConstructorHierarchyReady>,
+    mut commands: Commands,
+    scenes: Query<(), With<CellScene>>,
+) {
+    if scenes.contains(trigger.target()) {
+        commands
+            .entity(trigger.target())
+            .remove::<CellContentLoading>();
+    }
+}
+
+/// A cell without its scene file is left empty rather than loading forever.
+fn fail_cell_scenes(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    scenes: Query<(Entity, &SceneRoot), (With<CellScene>, With<CellContentLoading>)>,
+) {
+    for (entity, scene) in scenes.iter() {
+        if asset_server.load_state(&scene.0).is_failed() {
+            warn!(path = ?scene.0.path(), "failed to load cell scene, leaving the cell empty");
+            commands.entity(entity).remove::<CellContentLoading>();
+        }
+    }
+}
+
+fn finish_loading_cells(
+    mut cells: Query<(Entity, &mut Cell, Option<&Children>)>,
+    loading: Query<(), With<CellContentLoading>>,
+) {
+    for (entity, mut cell, children) in cells.iter_mut() {
+        if cell.loaded {
+            continue;
+        }
+
+        let mut content = std::iter::once(entity).chain(children.into_iter().flatten().copied());
+        if !content.any(|entity| loading.contains(entity)) {
+            debug!(?entity, coord = ?cell.coord, "cell loaded");
+            cell.loaded = true;
+        }
+    }
+}
+
+/// Cells are outlined at the player's height, the ground they cover can be anywhere.
+fn draw_cells(
+    mut gizmos: Gizmos,
+    roots: Query<(&Streaming, &GlobalTransform, &LoadedCells)>,
+    cells: Query<&Cell>,
+    player: Option<Single<&GlobalTransform, With<Player>>>,
+) {
+    let height = player.map_or(0.0, |player| player.translation().y);
+
+    for (streaming, transform, loaded) in roots.iter() {
+        for &entity in loaded.0.values() {
+            let Ok(cell) = cells.get(entity) else {
+                continue;
+            };
+
+            let centre = transform.transform_point(streaming.cell_translation(cell.coord));
+            let rotation =
+                transform.rotation() * Quat::from_rotation_x(std::f32::consts::FRAC_PI_2);
+            let color = match cell.loaded {
+                true => Color::srgb(0.2, 0.9, 0.3),
+                false => Color::srgb(1.0, 0.8, 0.2),
+            };
+            gizmos.rect(
+                Isometry3d::new(centre.with_y(height), rotation),
+                Vec2::splat(streaming.cell_size * 0.98),
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,678 @@
+//! Heightmap terrain generated from seeded fractal noise, so outdoor maps can be shaped without
+//! going back to Blender.
+//!
+//! A [`Terrain`] fills each cell its [`Streaming`] root loads with a chunk, generated off the main
+//! thread, with its own mesh, heightfield collider and splat map. Chunk meshes drop detail with
+//! distance from the player, their skirts hide the cracks between neighbours of different detail.
+//! Changing the component regenerates every chunk.
+
+use crate::gameplay::player::Player;
+use crate::gameplay::streaming::{Cell, CellContentLoading, ReloadCells, Streaming};
+use avian3d::prelude::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::ImageSampler;
+use bevy::pbr::{ExtendedMaterial, MaterialExtension};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::primitives::Aabb;
+use bevy::render::render_resource::{
+    AsBindGroup, Extent3d, ShaderRef, TextureDimension, TextureFormat,
+};
+use bevy::tasks::futures_lite::future;
+use bevy::tasks::{AsyncComputeTaskPool, Task, block_on};
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
+
+const TERRAIN_SHADER: &str = "shaders/terrain.wgsl";
+/// How far chunk skirts hang below the surface.
+const SKIRT_DEPTH: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Terrain>()
+        .register_type::<TerrainChunk>()
+        .register_type::<TerrainLayers>();
+
+    app.add_plugins(MaterialPlugin::<TerrainMaterial>::default());
+
+    app.init_resource::<TerrainLayers>();
+
+    app.add_observer(generate_chunk);
+
+    app.add_systems(
+        Update,
+        (
+            regenerate_terrain,
+            finish_chunks,
+            update_chunk_lods,
+            update_terrain_layers.run_if(resource_changed::<TerrainLayers>),
+        )
+            .chain(),
+    );
+}
+
+/// Settings for a generated terrain centred on the entity, e.g. placed in Blender through glTF
+/// extras as `Terrain` = `(seed: 7, height: 40.0)`. Its chunks are the entity's streaming cells.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default)]
+#[require(Streaming)]
+pub struct Terrain {
+    #[reflect(default)]
+    pub seed: u32,
+    /// Height of the highest peaks above the entity, valleys go as far below.
+    #[reflect(default = "defaults::height")]
+    pub height: f32,
+    /// Features per world unit of the first noise octave.
+    #[reflect(default = "defaults::frequency")]
+    pub frequency: f64,
+    #[reflect(default = "defaults::octaves")]
+    pub octaves: usize,
+    /// Frequency multiplier between octaves.
+    #[reflect(default = "defaults::lacunarity")]
+    pub lacunarity: f64,
+    /// Amplitude multiplier between octaves.
+    #[reflect(default = "defaults::persistence")]
+    pub persistence: f64,
+    /// Quads along a chunk's side at full detail, rounded up to a power of two.
+    #[reflect(default = "defaults::resolution")]
+    pub resolution: u32,
+    /// Distances from the player past which chunks halve their detail, nearest first.
+    #[reflect(default = "defaults::lod_distances")]
+    pub lod_distances: Vec<f32>,
+}
+
+mod defaults {
+    pub(super) fn height() -> f32 {
+        30.0
+    }
+
+    pub(super) fn frequency() -> f64 {
+        0.004
+    }
+
+    pub(super) fn octaves() -> usize {
+        5
+    }
+
+    pub(super) fn lacunarity() -> f64 {
+        2.0
+    }
+
+    pub(super) fn persistence() -> f64 {
+        0.5
+    }
+
+    pub(super) fn resolution() -> u32 {
+        64
+    }
+
+    pub(super) fn lod_distances() -> Vec<f32> {
+        vec![96.0, 192.0, 320.0]
+    }
+}
+
+impl Default for Terrain {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            height: defaults::height(),
+            frequency: defaults::frequency(),
+            octaves: defaults::octaves(),
+            lacunarity: defaults::lacunarity(),
+            persistence: defaults::persistence(),
+            resolution: defaults::resolution(),
+            lod_distances: defaults::lod_distances(),
+        }
+    }
+}
+
+impl Terrain {
+    fn resolution(&self) -> usize {
+        self.resolution.clamp(2, 1024).next_power_of_two() as usize
+    }
+
+    /// Coarsest detail level, the one where a chunk is a single quad.
+    fn max_lod(&self) -> u8 {
+        self.resolution().trailing_zeros() as u8
+    }
+
+    fn lod_at(&self, distance: f32) -> u8 {
+        let lod = self
+            .lod_distances
+            .iter()
+            .filter(|&&lod_distance| distance > lod_distance)
+            .count();
+        (lod as u8).min(self.max_lod())
+    }
+}
+
+/// Generated part of a [`Terrain`], on one of its cells.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct TerrainChunk {
+    /// Detail level of the mesh, each level halves the quads along a side.
+    pub lod: u8,
+}
+
+/// Everything about a chunk that is slow to make, made off the main thread.
+struct ChunkData {
+    grid: HeightGrid,
+    collider: Collider,
+    splat_map: Image,
+}
+
+#[derive(Component)]
+struct GenerateChunk(Task<ChunkData>);
+
+/// Base colours of the splat map layers, blended by height and slope.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct TerrainLayers {
+    pub sand: Color,
+    pub grass: Color,
+    pub rock: Color,
+    pub snow: Color,
+}
+
+impl Default for TerrainLayers {
+    fn default() -> Self {
+        Self {
+            sand: Color::srgb(0.76, 0.7, 0.5),
+            grass: Color::srgb(0.3, 0.45, 0.2),
+            rock: Color::srgb(0.42, 0.4, 0.38),
+            snow: Color::srgb(0.92, 0.93, 0.95),
+        }
+    }
+}
+
+pub type TerrainMaterial = ExtendedMaterial<StandardMaterial, SplatMaterial>;
+
+/// Blends the layer colours with the weights in a chunk's splat map, red to alpha for sand,
+/// grass, rock and snow.
+#[derive(Asset, TypePath, AsBindGroup, Clone, Debug)]
+pub struct SplatMaterial {
+    #[uniform(100)]
+    sand: LinearRgba,
+    #[uniform(100)]
+    grass: LinearRgba,
+    #[uniform(100)]
+    rock: LinearRgba,
+    #[uniform(100)]
+    snow: LinearRgba,
+    #[texture(101)]
+    #[sampler(102)]
+    splat_map: Handle<Image>,
+}
+
+impl SplatMaterial {
+    fn new(layers: &TerrainLayers, splat_map: Handle<Image>) -> Self {
+        Self {
+            sand: layers.sand.to_linear(),
+            grass: layers.grass.to_linear(),
+            rock: layers.rock.to_linear(),
+            snow: layers.snow.to_linear(),
+            splat_map,
+        }
+    }
+}
+
+impl MaterialExtension for SplatMaterial {
+    fn fragment_shader() -> ShaderRef {
+        TERRAIN_SHADER.into()
+    }
+
+    fn deferred_fragment_shader() -> ShaderRef {
+        TERRAIN_SHADER.into()
+    }
+}
+
+/// Seeded fractal noise the terrain's heights are sampled from.
+#[derive(Clone)]
+pub struct HeightNoise {
+    fbm: Fbm<Perlin>,
+    height: f32,
+}
+
+impl HeightNoise {
+    pub fn new(terrain: &Terrain) -> Self {
+        Self {
+            fbm: Fbm::new(terrain.seed)
+                .set_octaves(terrain.octaves.clamp(1, Fbm::<Perlin>::MAX_OCTAVES))
+                .set_frequency(terrain.frequency)
+                .set_lacunarity(terrain.lacunarity)
+                .set_persistence(terrain.persistence),
+            height: terrain.height,
+        }
+    }
+
+    /// Height at a point on the terrain, relative to the terrain entity.
+    pub fn sample(&self, x: f32, z: f32) -> f32 {
+        self.fbm.get([x as f64, z as f64]) as f32 * self.height
+    }
+}
+
+/// Full detail heights of a chunk, with a ring of samples from its neighbours so normals match
+/// across chunk borders.
+#[derive(Component, Debug)]
+pub struct HeightGrid {
+    /// Samples along a side, not counting the ring.
+    samples: usize,
+    spacing: f32,
+    heights: Vec<f32>,
+}
+
+impl HeightGrid {
+    /// Samples a `size` wide square with `resolution` quads along a side, centred on `centre`.
+    pub fn generate(noise: &HeightNoise, resolution: usize, size: f32, centre: Vec2) -> Self {
+        let samples = resolution + 1;
+        let spacing = size / resolution as f32;
+        let corner = centre - Vec2::splat(size / 2.0);
+
+        let ring = samples + 2;
+        let mut heights = Vec::with_capacity(ring * ring);
+        for z in 0..ring {
+            for x in 0..ring {
+                heights.push(noise.sample(
+                    corner.x + (x as f32 - 1.0) * spacing,
+                    corner.y + (z as f32 - 1.0) * spacing,
+                ));
+            }
+        }
+
+        Self {
+            samples,
+            spacing,
+            heights,
+        }
+    }
+
+    fn height(&self, x: usize, z: usize) -> f32 {
+        self.heights[(z + 1) * (self.samples + 2) + x + 1]
+    }
+
+    fn normal(&self, x: usize, z: usize) -> Vec3 {
+        let ring = self.samples + 2;
+        let at = |x: usize, z: usize| self.heights[z * ring + x];
+        let (x, z) = (x + 1, z + 1);
+        Vec3::new(
+            at(x - 1, z) - at(x + 1, z),
+            2.0 * self.spacing,
+            at(x, z - 1) - at(x, z + 1),
+        )
+        .normalize()
+    }
+
+    /// Heightfield matching the full detail mesh, centred on the chunk.
+    pub fn collider(&self) -> Collider {
+        let heights = (0..self.samples)
+            .map(|x| (0..self.samples).map(|z| self.height(x, z)).collect())
+            .collect();
+        let size = self.spacing * (self.samples - 1) as f32;
+        Collider::heightfield(heights, Vec3::new(size, 1.0, size))
+    }
+
+    /// Mesh centred on the chunk keeping every `2^lod`th sample, with a skirt around its edge.
+    pub fn mesh(&self, lod: u8) -> Mesh {
+        let step = 1 << lod;
+        let quads = (self.samples - 1) / step;
+        let side = quads + 1;
+        let half = self.spacing * (self.samples - 1) as f32 / 2.0;
+
+        let mut positions = Vec::with_capacity(side * side);
+        let mut normals = Vec::with_capacity(side * side);
+        let mut uvs = Vec::with_capacity(side * side);
+        for z in (0..self.samples).step_by(step) {
+            for x in (0..self.samples).step_by(step) {
+                positions.push([
+                    x as f32 * self.spacing - half,
+                    self.height(x, z),
+                    z as f32 * self.spacing - half,
+                ]);
+                normals.push(self.normal(x, z).to_array());
+                uvs.push([
+                    x as f32 / (self.samples - 1) as f32,
+                    z as f32 / (self.samples - 1) as f32,
+                ]);
+            }
+        }
+
+        let mut indices = Vec::with_capacity(quads * quads * 6);
+        for z in 0..quads {
+            for x in 0..quads {
+                let i = (z * side + x) as u32;
+                let below = i + side as u32;
+                indices.extend([i, below, i + 1, i + 1, below, below + 1]);
+            }
+        }
+
+        // Walked counter-clockwise from above, so every skirt quad faces out of the chunk.
+        let edge: Vec<usize> = (0..quads)
+            .chain((0..quads).map(|z| z * side + quads))
+            .chain((1..=quads).rev().map(|x| quads * side + x))
+            .chain((1..=quads).rev().map(|z| z * side))
+            .collect();
+        let skirt_start = positions.len();
+        for &top in edge.iter() {
+            let [x, y, z] = positions[top];
+            positions.push([x, y - SKIRT_DEPTH, z]);
+            normals.push(normals[top]);
+            uvs.push(uvs[top]);
+        }
+        for (i, &top) in edge.iter().enumerate() {
+            let next = (i + 1) % edge.len();
+            let (a, b) = (top as u32, edge[next] as u32);
+            let (skirt_a, skirt_b) = ((skirt_start + i) as u32, (skirt_start + next) as u32);
+            indices.extend([a, b, skirt_a, b, skirt_b, skirt_a]);
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+
+    /// One texel per sample, weighted towards sand low down, snow up high and rock on slopes.
+    pub fn splat_map(&self, height: f32) -> Image {
+        let mut data = Vec::with_capacity(self.samples * self.samples * 4);
+        for z in 0..self.samples {
+            for x in 0..self.samples {
+                let altitude = (self.height(x, z) / height.max(f32::EPSILON) + 1.0) / 2.0;
+                let steepness = 1.0 - self.normal(x, z).y;
+
+                let sand = 1.0 - smoothstep(0.3, 0.38, altitude);
+                let snow = smoothstep(0.72, 0.8, altitude);
+                let rock = smoothstep(0.15, 0.3, steepness) * (1.0 - snow);
+                let grass = (1.0 - sand - snow - rock).max(0.0);
+
+                // Rounding the running total keeps the texel summing to exactly 255.
+                let total = sand + grass + rock + snow;
+                let mut cumulative = 0.0;
+                let mut previous = 0;
+                data.extend([sand, grass, rock, snow].map(|weight| {
+                    cumulative += weight / total * 255.0;
+                    let rounded = cumulative.round() as u8;
+                    let texel = rounded - previous;
+                    previous = rounded;
+                    texel
+                }));
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: self.samples as u32,
+                height: self.samples as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        image.sampler = ImageSampler::linear();
+        image
+    }
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn regenerate_terrain(mut commands: Commands, terrains: Query<(Entity, Ref<Terrain>)>) {
+    for (entity, terrain) in terrains.iter() {
+        if terrain.is_changed() && !terrain.is_added() {
+            debug!(?entity, seed = terrain.seed, "regenerating terrain");
+            commands.trigger_targets(ReloadCells, entity);
+        }
+    }
+}
+
+fn generate_chunk(
+    trigger: Trigger<OnAdd, Cell>,
+    mut commands: Commands,
+    cells: Query<(&Cell, &ChildOf)>,
+    terrains: Query<(&Terrain, &Streaming)>,
+) {
+    let cell = trigger.target();
+    let Ok((Cell { coord, .. }, child_of)) = cells.get(cell) else {
+        return;
+    };
+    let Ok((terrain, streaming)) = terrains.get(child_of.parent()) else {
+        return;
+    };
+
+    let terrain = terrain.clone();
+    let size = streaming.cell_size;
+    let centre = streaming.cell_translation(*coord).xz();
+    let task = AsyncComputeTaskPool::get().spawn(async move {
+        let noise = HeightNoise::new(&terrain);
+        let grid = HeightGrid::generate(&noise, terrain.resolution(), size, centre);
+        ChunkData {
+            collider: grid.collider(),
+            splat_map: grid.splat_map(terrain.height),
+            grid,
+        }
+    });
+
+    commands
+        .entity(cell)
+        .insert((GenerateChunk(task), CellContentLoading));
+}
+
+fn finish_chunks(
+    mut commands: Commands,
+    player: Option<Single<&GlobalTransform, With<Player>>>,
+    terrains: Query<&Terrain>,
+    mut chunks: Query<(Entity, &mut GenerateChunk, &GlobalTransform, &ChildOf)>,
+    layers: Res<TerrainLayers>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+) {
+    for (entity, mut task, transform, child_of) in chunks.iter_mut() {
+        // Looked up before polling, a finished task must not be polled again.
+        let Ok(terrain) = terrains.get(child_of.parent()) else {
+            warn!(?entity, "terrain chunk outside a terrain, dropping it");
+            commands
+                .entity(entity)
+                .remove::<(GenerateChunk, CellContentLoading)>();
+            continue;
+        };
+        let Some(data) = block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+
+        let lod = match &player {
+            Some(player) => terrain.lod_at(
+                transform
+                    .translation()
+                    .xz()
+                    .distance(player.translation().xz()),
+            ),
+            None => terrain.lod_at(f32::INFINITY),
+        };
+        let material = TerrainMaterial {
+            base: StandardMaterial {
+                perceptual_roughness: 0.9,
+                ..default()
+            },
+            extension: SplatMaterial::new(&layers, images.add(data.splat_map)),
+        };
+
+        commands
+            .entity(entity)
+            .remove::<(GenerateChunk, CellContentLoading)>()
+            .insert((
+                TerrainChunk { lod },
+                Mesh3d(meshes.add(data.grid.mesh(lod))),
+                MeshMaterial3d(materials.add(material)),
+                RigidBody::Static,
+                data.collider,
+                data.grid,
+            ));
+    }
+}
+
+fn update_chunk_lods(
+    mut commands: Commands,
+    player: Option<Single<&GlobalTransform, With<Player>>>,
+    terrains: Query<&Terrain>,
+    mut chunks: Query<(
+        Entity,
+        &mut TerrainChunk,
+        &HeightGrid,
+        &GlobalTransform,
+        &ChildOf,
+        &mut Mesh3d,
+    )>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+    let player = player.translation().xz();
+
+    for (entity, mut chunk, grid, transform, child_of, mut mesh) in chunks.iter_mut() {
+        let Ok(terrain) = terrains.get(child_of.parent()) else {
+            continue;
+        };
+
+        let lod = terrain.lod_at(transform.translation().xz().distance(player));
+        if lod == chunk.lod {
+            continue;
+        }
+
+        chunk.lod = lod;
+        mesh.0 = meshes.add(grid.mesh(lod));
+        // Bounds are only computed for meshes without them, the skirt changes with the detail.
+        commands.entity(entity).remove::<Aabb>();
+    }
+}
+
+fn update_terrain_layers(
+    layers: Res<TerrainLayers>,
+    chunks: Query<&MeshMaterial3d<TerrainMaterial>, With<TerrainChunk>>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+) {
+    for handle in chunks.iter() {
+        if let Some(material) = materials.get_mut(handle) {
+            let splat_map = material.extension.splat_map.clone();
+            material.extension = SplatMaterial::new(&layers, splat_map);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grid of `resolution` quads along a side, one unit apart, with heights from `height`.
+    fn grid(resolution: usize, height: impl Fn(f32, f32) -> f32) -> HeightGrid {
+        let samples = resolution + 1;
+        let ring = samples + 2;
+        let heights = (0..ring * ring)
+            .map(|i| height((i % ring) as f32 - 1.0, (i / ring) as f32 - 1.0))
+            .collect();
+        HeightGrid {
+            samples,
+            spacing: 1.0,
+            heights,
+        }
+    }
+
+    fn positions(mesh: &Mesh) -> &[[f32; 3]] {
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|positions| positions.as_float3())
+            .unwrap()
+    }
+
+    fn triangles(mesh: &Mesh) -> Vec<[Vec3; 3]> {
+        let positions = positions(mesh);
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        indices
+            .chunks_exact(3)
+            .map(|triangle| triangle.map(|i| Vec3::from(positions[i])))
+            .collect()
+    }
+
+    #[test]
+    fn mesh_counts_per_lod() {
+        let grid = grid(8, |_, _| 0.0);
+
+        for (lod, quads) in [(0, 8), (1, 4), (2, 2), (3, 1)] {
+            let mesh = grid.mesh(lod);
+            let edge = quads * 4;
+            assert_eq!(
+                mesh.count_vertices(),
+                (quads + 1) * (quads + 1) + edge,
+                "lod {lod}"
+            );
+            assert_eq!(
+                mesh.indices().unwrap().len(),
+                (quads * quads + edge) * 6,
+                "lod {lod}"
+            );
+        }
+    }
+
+    #[test]
+    fn mesh_faces_up_and_skirt_faces_out() {
+        let grid = grid(4, |_, _| 0.0);
+        let triangles = triangles(&grid.mesh(0));
+        let (top, skirt) = triangles.split_at(4 * 4 * 2);
+
+        for &[a, b, c] in top {
+            let normal = (b - a).cross(c - a);
+            assert!(normal.y > 0.0, "top triangle {a} {b} {c} faces down");
+        }
+
+        for &[a, b, c] in skirt {
+            let normal = (b - a).cross(c - a);
+            let outwards = ((a + b + c) / 3.0).with_y(0.0);
+            assert!(
+                normal.dot(outwards) > 0.0,
+                "skirt triangle {a} {b} {c} faces into the chunk"
+            );
+            assert!(a.y.min(b.y).min(c.y) < -SKIRT_DEPTH / 2.0);
+        }
+    }
+
+    #[test]
+    fn collider_matches_grid_heights() {
+        // Slopes differently along x and z so a transposed heightfield is caught.
+        let grid = grid(4, |x, z| x + z * 10.0);
+        let collider = grid.collider();
+        let half = 2.0;
+
+        for z in 0..grid.samples {
+            for x in 0..grid.samples {
+                let origin = Vec3::new(x as f32 - half, 100.0, z as f32 - half);
+                let (distance, _) = collider
+                    .cast_ray(Vec3::ZERO, Quat::IDENTITY, origin, Vec3::NEG_Y, 200.0, true)
+                    .unwrap();
+                let height = origin.y - distance;
+                assert!(
+                    (height - grid.height(x, z)).abs() < 1e-3,
+                    "height at {x} {z} is {height}, expected {}",
+                    grid.height(x, z)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn splat_weights_sum_to_255() {
+        let grid = grid(16, |x, z| (x * 0.7).sin() * 20.0 + (z * 0.3).cos() * x);
+        let image = grid.splat_map(20.0);
+        let data = image.data.unwrap();
+
+        assert_eq!(data.len(), 17 * 17 * 4);
+        for texel in data.chunks_exact(4) {
+            let sum: u32 = texel.iter().map(|&weight| weight as u32).sum();
+            assert_eq!(sum, 255, "texel {texel:?}");
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::GameState;
+use crate::gameplay::input::{InventoryActions, PauseActions, PlayerActions, SettingsActions};
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<GameInput>();
+
+    app.add_observer(change_game_state);
+}
+
+/// Entity holding the input context of the current [`GameState`].
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub(super) struct GameInput;
+
+/// Asks to move to another [`GameState`], ignored unless [`can_transition`] allows it.
+#[derive(Event, Debug, Clone, Copy)]
+pub(super) struct RequestGameState(pub GameState);
+
+/// Every allowed change of [`GameState`].
+const TRANSITIONS: &[(GameState, GameState)] = &[
+    (GameState::Playing, GameState::Inventory),
+    (GameState::Inventory, GameState::Playing),
+    (GameState::Playing, GameState::Paused),
+    (GameState::Paused, GameState::Playing),
+    (GameState::Paused, GameState::Settings),
+    (GameState::Settings, GameState::Paused),
+];
+
+pub(super) fn can_transition(from: GameState, to: GameState) -> bool {
+    TRANSITIONS.contains(&(from, to))
+}
+
+fn change_game_state(
+    trigger: Trigger<RequestGameState>,
+    mut commands: Commands,
+    curr_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    input: Single<Entity, With<GameInput>>,
+) {
+    let from = *curr_state.get();
+    let to = trigger.event().0;
+
+    // Two requests in one frame, e.g. from a double keypress, would both be checked against the
+    // state that is about to be left.
+    if let NextState::Pending(pending) = *next_state {
+        warn!(
+            ?from,
+            ?pending,
+            ?to,
+            "state transition already pending, ignoring"
+        );
+        return;
+    }
+
+    if !can_transition(from, to) {
+        warn!(?from, ?to, "rejected invalid state transition");
+        return;
+    }
+
+    debug!(?from, ?to, "changing game state");
+
+    let mut input = commands.entity(*input);
+    input.remove::<(
+        Actions<PlayerActions>,
+        Actions<InventoryActions>,
+        Actions<PauseActions>,
+        Actions<SettingsActions>,
+    )>();
+    match to {
+        GameState::Playing => input.insert(Actions::<PlayerActions>::default()),
+        GameState::Inventory => input.insert(Actions::<InventoryActions>::default()),
+        GameState::Paused => input.insert(Actions::<PauseActions>::default()),
+        GameState::Settings => input.insert(Actions::<SettingsActions>::default()),
+    };
+
+    next_state.set(to);
+}
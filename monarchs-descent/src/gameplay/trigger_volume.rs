@@ -0,0 +1,397 @@
+use crate::AppState;
+use crate::gameplay::door::{DoorState, OpenDoor};
+use crate::gameplay::level_exit::LeaveLevel;
+use crate::gameplay::player::Player;
+use crate::loading::GameAssets;
+use avian3d::prelude::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TriggerVolume>()
+        .register_type::<TriggerState>()
+        .register_type::<Tags>();
+
+    app.init_asset::<TriggerSet>()
+        .init_asset_loader::<TriggerSetLoader>();
+
+    app.add_observer(run_enter_actions)
+        .add_observer(run_exit_actions)
+        .add_observer(run_stay_actions);
+
+    app.add_systems(OnEnter(AppState::Gameplay), spawn_level_triggers)
+        .add_systems(
+            Update,
+            (
+                detect_trigger_volumes,
+                tick_trigger_volumes,
+                expire_trigger_messages,
+            )
+                .run_if(in_state(AppState::Gameplay)),
+        );
+}
+
+/// Sensor that runs actions as things enter, stay in and leave it. Placed in Blender through glTF
+/// extras, e.g. `TriggerVolume` = `(on_enter: [ShowMessage(text: "Hello", seconds: 2.0)])`, or
+/// listed in a level's `.triggers.ron` file.
+#[derive(Component, Reflect, Serialize, Deserialize, Default, Debug, Clone)]
+#[reflect(Component, Default)]
+#[require(Sensor, CollisionEventsEnabled, TriggerState)]
+#[serde(default)]
+pub struct TriggerVolume {
+    #[reflect(default)]
+    pub filter: TriggerFilter,
+    #[reflect(default)]
+    pub mode: TriggerMode,
+    /// Seconds between stay events while something is inside, every frame when zero.
+    #[reflect(default)]
+    pub stay_interval: f32,
+    #[reflect(default)]
+    pub on_enter: Vec<TriggerAction>,
+    #[reflect(default)]
+    pub on_stay: Vec<TriggerAction>,
+    #[reflect(default)]
+    pub on_exit: Vec<TriggerAction>,
+}
+
+/// What can set off a [`TriggerVolume`].
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub enum TriggerFilter {
+    #[default]
+    Player,
+    /// Bodies with this tag in their [`Tags`].
+    Tag(String),
+    Any,
+}
+
+#[derive(Reflect, Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerMode {
+    #[default]
+    Repeatable,
+    /// Disables the volume once everything that entered it has left, so its events fire for one
+    /// visit only.
+    Once,
+}
+
+/// Built in responses to a trigger event, anything more specific can observe the events directly.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone)]
+pub enum TriggerAction {
+    /// Spawns the first scene of a glTF file.
+    SpawnScene {
+        path: String,
+        translation: Vec3,
+    },
+    ShowMessage {
+        text: String,
+        seconds: f32,
+    },
+    /// Opens the sliding door with this name.
+    OpenDoor {
+        door: String,
+    },
+    /// Moves whatever set off the trigger.
+    Teleport {
+        translation: Vec3,
+    },
+    ChangeLevel {
+        level: String,
+    },
+}
+
+/// Labels a [`TriggerFilter::Tag`] can match.
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component, Default)]
+pub struct Tags(pub Vec<String>);
+
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component)]
+pub struct TriggerState {
+    occupants: Vec<Entity>,
+    since_stay: f32,
+    spent: bool,
+}
+
+/// Triggered on a [`TriggerVolume`] when `actor` enters it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerEntered {
+    pub actor: Entity,
+}
+
+/// Triggered on a [`TriggerVolume`] every stay interval for each `actor` inside it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerStayed {
+    pub actor: Entity,
+}
+
+/// Triggered on a [`TriggerVolume`] when `actor` leaves it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerExited {
+    pub actor: Entity,
+}
+
+/// Trigger volumes listed in a `.triggers.ron` file, spawned with the level.
+#[derive(Asset, TypePath, Deserialize, Debug)]
+#[serde(transparent)]
+pub(crate) struct TriggerSet(pub Vec<PlacedTrigger>);
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct PlacedTrigger {
+    pub name: String,
+    pub translation: Vec3,
+    /// Full size of the box shaped sensor.
+    pub size: Vec3,
+    pub volume: TriggerVolume,
+}
+
+#[derive(Default)]
+struct TriggerSetLoader;
+
+impl AssetLoader for TriggerSetLoader {
+    type Asset = TriggerSet;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<TriggerSet, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["triggers.ron"]
+    }
+}
+
+fn spawn_level_triggers(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    trigger_sets: Res<Assets<TriggerSet>>,
+) {
+    // A set that failed to load was already reported by the loading screen.
+    let Some(set) = assets
+        .triggers
+        .as_ref()
+        .and_then(|handle| trigger_sets.get(handle))
+    else {
+        return;
+    };
+
+    for trigger in set.0.iter() {
+        commands.spawn((
+            Name::new(trigger.name.clone()),
+            StateScoped(AppState::Gameplay),
+            Transform::from_translation(trigger.translation),
+            Collider::cuboid(trigger.size.x, trigger.size.y, trigger.size.z),
+            trigger.volume.clone(),
+        ));
+    }
+}
+
+#[derive(Component, Debug)]
+struct TriggerMessage(Timer);
+
+fn detect_trigger_volumes(
+    mut commands: Commands,
+    mut started: EventReader<CollisionStarted>,
+    mut ended: EventReader<CollisionEnded>,
+    mut volumes: Query<(&TriggerVolume, &mut TriggerState)>,
+    colliders: Query<&ColliderOf>,
+    players: Query<(), With<Player>>,
+    tags: Query<&Tags>,
+) {
+    let started = started.read().map(|CollisionStarted(a, b)| (*a, *b, true));
+    let ended = ended.read().map(|CollisionEnded(a, b)| (*a, *b, false));
+
+    for (a, b, entered) in started.chain(ended) {
+        let (volume, other) = match volumes.contains(a) {
+            true => (a, b),
+            false => (b, a),
+        };
+        let Ok((trigger, mut state)) = volumes.get_mut(volume) else {
+            continue;
+        };
+
+        let actor = colliders
+            .get(other)
+            .map_or(other, |collider_of| collider_of.body);
+        let matches = match &trigger.filter {
+            TriggerFilter::Player => players.contains(actor),
+            TriggerFilter::Tag(tag) => tags.get(actor).is_ok_and(|tags| tags.0.contains(tag)),
+            TriggerFilter::Any => true,
+        };
+        if !matches {
+            continue;
+        }
+
+        match entered {
+            true => {
+                // A body with several colliders enters once per collider.
+                if state.spent || state.occupants.contains(&actor) {
+                    continue;
+                }
+                state.occupants.push(actor);
+                commands.trigger_targets(TriggerEntered { actor }, volume);
+            }
+            false => {
+                let Some(index) = state
+                    .occupants
+                    .iter()
+                    .position(|&occupant| occupant == actor)
+                else {
+                    continue;
+                };
+                state.occupants.swap_remove(index);
+                commands.trigger_targets(TriggerExited { actor }, volume);
+
+                if trigger.mode == TriggerMode::Once && state.occupants.is_empty() {
+                    debug!(?volume, "one shot trigger spent");
+                    state.spent = true;
+                }
+            }
+        }
+    }
+}
+
+fn tick_trigger_volumes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut volumes: Query<(Entity, &TriggerVolume, &mut TriggerState)>,
+) {
+    for (volume, trigger, mut state) in volumes.iter_mut() {
+        if state.occupants.is_empty() {
+            state.since_stay = 0.0;
+            continue;
+        }
+
+        state.since_stay += time.delta_secs();
+        if state.since_stay < trigger.stay_interval {
+            continue;
+        }
+        state.since_stay = 0.0;
+
+        for &actor in state.occupants.iter() {
+            commands.trigger_targets(TriggerStayed { actor }, volume);
+        }
+    }
+}
+
+fn run_actions(commands: &mut Commands, actions: &[TriggerAction], actor: Entity) {
+    for action in actions.iter().cloned() {
+        commands.queue(move |world: &mut World| run_action(world, action, actor));
+    }
+}
+
+fn run_enter_actions(
+    trigger: Trigger<TriggerEntered>,
+    mut commands: Commands,
+    volumes: Query<&TriggerVolume>,
+) {
+    if let Ok(volume) = volumes.get(trigger.target()) {
+        run_actions(&mut commands, &volume.on_enter, trigger.event().actor);
+    }
+}
+
+fn run_stay_actions(
+    trigger: Trigger<TriggerStayed>,
+    mut commands: Commands,
+    volumes: Query<&TriggerVolume>,
+) {
+    if let Ok(volume) = volumes.get(trigger.target()) {
+        run_actions(&mut commands, &volume.on_stay, trigger.event().actor);
+    }
+}
+
+fn run_exit_actions(
+    trigger: Trigger<TriggerExited>,
+    mut commands: Commands,
+    volumes: Query<&TriggerVolume>,
+) {
+    if let Ok(volume) = volumes.get(trigger.target()) {
+        run_actions(&mut commands, &volume.on_exit, trigger.event().actor);
+    }
+}
+
+fn run_action(world: &mut World, action: TriggerAction, actor: Entity) {
+    debug!(?action, ?actor, "running trigger action");
+
+    match action {
+        TriggerAction::SpawnScene { path, translation } => {
+            let scene = world
+                .resource::<AssetServer>()
+                .load(GltfAssetLabel::Scene(0).from_asset(path.clone()));
+            world.spawn((
+                Name::new(path),
+                StateScoped(AppState::Gameplay),
+                Transform::from_translation(translation),
+                SceneRoot(scene),
+            ));
+        }
+        TriggerAction::ShowMessage { text, seconds } => show_message(world, text, seconds),
+        TriggerAction::OpenDoor { door } => {
+            let found = world
+                .query_filtered::<(Entity, &Name), With<DoorState>>()
+                .iter(world)
+                .find(|(_, name)| name.as_str() == door)
+                .map(|(entity, _)| entity);
+            match found {
+                Some(entity) => world.trigger_targets(OpenDoor, entity),
+                None => warn!(door, "no door with this name"),
+            }
+        }
+        TriggerAction::Teleport { translation } => {
+            let Ok(mut entity) = world.get_entity_mut(actor) else {
+                return;
+            };
+            if let Some(mut transform) = entity.get_mut::<Transform>() {
+                transform.translation = translation;
+            }
+            if let Some(mut velocity) = entity.get_mut::<LinearVelocity>() {
+                *velocity = LinearVelocity::ZERO;
+            }
+        }
+        TriggerAction::ChangeLevel { level } => world.trigger(LeaveLevel { level }),
+    }
+}
+
+/// Shows `text` at the top of the screen for `seconds`, replacing any earlier message.
+fn show_message(world: &mut World, text: String, seconds: f32) {
+    let previous: Vec<Entity> = world
+        .query_filtered::<Entity, With<TriggerMessage>>()
+        .iter(world)
+        .collect();
+    for entity in previous {
+        world.despawn(entity);
+    }
+
+    world.spawn((
+        Name::new("Trigger Message"),
+        TriggerMessage(Timer::from_seconds(seconds, TimerMode::Once)),
+        StateScoped(AppState::Gameplay),
+        Node {
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            padding: UiRect::top(Val::Px(48.0)),
+            ..default()
+        },
+        children![(Text::new(text), TextFont::from_font_size(24.0))],
+    ));
+}
+
+fn expire_trigger_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut messages: Query<(Entity, &mut TriggerMessage)>,
+) {
+    for (entity, mut message) in messages.iter_mut() {
+        if message.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use crate::AppState;
+use bevy::prelude::*;
+
+/// Level started from the main menu.
+pub(crate) const FIRST_LEVEL: &str = "basic_surface";
+/// Streamed terrain level the first level's exit leads to.
+pub(crate) const HIGHLANDS_LEVEL: &str = "highlands";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CurrentLevel>();
+
+    app.init_resource::<CurrentLevel>();
+
+    app.add_level(Level {
+        name: FIRST_LEVEL,
+        map: "maps/basic_surface.glb",
+        triggers: Some("levels/basic_surface.triggers.ron"),
+    })
+    .add_level(Level {
+        name: HIGHLANDS_LEVEL,
+        // Only a terrain and a spawn point set through glTF extras, the terrain is generated.
+        map: "maps/highlands.gltf",
+        triggers: None,
+    });
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Level {
+    pub name: &'static str,
+    /// glTF file the level's first scene is spawned from.
+    pub map: &'static str,
+    /// RON file listing the level's trigger volumes, see [`TriggerVolume`].
+    ///
+    /// [`TriggerVolume`]: crate::gameplay::trigger_volume::TriggerVolume
+    pub triggers: Option<&'static str>,
+}
+
+/// Every level that can be loaded, in registration order.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct Levels(Vec<Level>);
+
+impl Levels {
+    pub(crate) fn get(&self, name: &str) -> Option<&Level> {
+        self.0.iter().find(|level| level.name == name)
+    }
+}
+
+pub(crate) trait LevelAppExt {
+    fn add_level(&mut self, level: Level) -> &mut Self;
+}
+
+impl LevelAppExt for App {
+    fn add_level(&mut self, level: Level) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<Levels>()
+            .0
+            .push(level);
+        self
+    }
+}
+
+/// Name of the level that is loading or being played.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub(crate) struct CurrentLevel(pub String);
+
+impl Default for CurrentLevel {
+    fn default() -> Self {
+        Self(FIRST_LEVEL.to_string())
+    }
+}
+
+/// Tears down the current level, everything scoped to [`AppState::Gameplay`], and loads the named
+/// level behind the loading screen.
+#[derive(Debug)]
+pub(crate) struct LoadLevel(pub String);
+
+impl Command for LoadLevel {
+    fn apply(self, world: &mut World) {
+        let LoadLevel(name) = self;
+        if world.resource::<Levels>().get(&name).is_none() {
+            error!(name, "no level with this name");
+            return;
+        }
+
+        info!(name, "loading level");
+
+        world.resource_mut::<CurrentLevel>().0 = name;
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Loading);
+    }
+}
@@ -1,10 +1,13 @@
 mod editor;
 mod gameplay;
+mod level;
+mod loading;
+mod main_menu;
 mod third_party;
 mod ui_camera;
+mod widgets;
 
 use bevy::prelude::*;
-use bevy::window::WindowMode;
 use bevy::{log, log::LogPlugin};
 use bevy_console::make_layer;
 
@@ -18,7 +21,6 @@ impl Plugin for AppPlugin {
                     primary_window: Some(Window {
                         title: "Monarchs descent".to_string(),
                         fit_canvas_to_parent: true,
-                        mode: WindowMode::BorderlessFullscreen(MonitorSelection::Primary),
                         ..default()
                     }),
                     ..default()
@@ -30,8 +32,7 @@ impl Plugin for AppPlugin {
                 }),
         );
 
-        app.insert_state(AppState::Gameplay)
-            .add_sub_state::<GameState>();
+        app.init_state::<AppState>().add_sub_state::<GameState>();
 
         // Third party plugins
         app.add_plugins(third_party::plugin);
@@ -40,14 +41,22 @@ impl Plugin for AppPlugin {
         app.add_plugins(editor::plugin);
 
         // Gameplay plugins
-        app.add_plugins(gameplay::plugin)
-            .add_plugins(ui_camera::plugin);
+        app.add_plugins(main_menu::plugin)
+            .add_plugins(level::plugin)
+            .add_plugins(loading::plugin)
+            .add_plugins(gameplay::plugin)
+            .add_plugins(ui_camera::plugin)
+            .add_plugins(widgets::plugin);
     }
 }
 
-#[derive(States, Hash, Eq, PartialEq, Clone, Copy, Debug)]
+#[derive(States, Hash, Eq, PartialEq, Clone, Copy, Debug, Default)]
 #[states(scoped_entities)]
-enum AppState {
+pub(crate) enum AppState {
+    #[default]
+    MainMenu,
+    /// Waiting on the map and item assets before the world is spawned.
+    Loading,
     Gameplay,
 }
 
@@ -55,9 +64,10 @@ enum AppState {
 #[source(AppState = AppState::Gameplay)]
 #[states(scoped_entities)]
 pub(crate) enum GameState {
+    #[default]
     Playing,
     Inventory,
-    #[default]
+    Paused,
     Settings,
 }
 
@@ -76,3 +86,4 @@ impl From<CameraOrder> for isize {
 pub(crate) const DEFAULT_RENDER_LAYER: usize = 0;
 pub(crate) const VIEW_MODEL_RENDER_LAYER: usize = 1;
 pub(crate) const UI_RENDER_LAYER: usize = 2;
+pub(crate) const PLAYER_BODY_RENDER_LAYER: usize = 3;
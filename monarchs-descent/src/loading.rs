@@ -0,0 +1,180 @@
+use crate::AppState;
+use crate::gameplay::trigger_volume::TriggerSet;
+use crate::level::{CurrentLevel, Level, Levels};
+use bevy::asset::{RecursiveDependencyLoadState, UntypedAssetId};
+use bevy::prelude::*;
+
+const GUN_PATH: &str = "weapons/basic_gun.glb";
+const BAR_WIDTH: f32 = 400.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LoadingProgress>();
+
+    app.add_systems(
+        OnEnter(AppState::Loading),
+        (start_loading, spawn_loading_screen),
+    )
+    .add_systems(
+        Update,
+        (track_loading, update_progress_bar)
+            .chain()
+            .run_if(in_state(AppState::Loading)),
+    );
+}
+
+/// Every asset the current level is spawned from, loaded before leaving [`AppState::Loading`]
+/// and held until the next level is loaded so they stay loaded.
+#[derive(Resource, Debug)]
+pub(crate) struct GameAssets {
+    pub map: Handle<Scene>,
+    pub gun: Handle<Scene>,
+    pub triggers: Option<Handle<TriggerSet>>,
+}
+
+impl GameAssets {
+    fn load(asset_server: &AssetServer, level: &Level) -> Self {
+        Self {
+            map: asset_server.load(GltfAssetLabel::Scene(0).from_asset(level.map)),
+            gun: asset_server.load(GltfAssetLabel::Scene(0).from_asset(GUN_PATH)),
+            triggers: level.triggers.map(|path| asset_server.load(path)),
+        }
+    }
+
+    fn ids(&self) -> Vec<UntypedAssetId> {
+        let mut ids = vec![self.map.id().untyped(), self.gun.id().untyped()];
+        ids.extend(self.triggers.as_ref().map(|handle| handle.id().untyped()));
+        ids
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct LoadingProgress {
+    loaded: usize,
+    total: usize,
+}
+
+impl LoadingProgress {
+    fn fraction(&self) -> f32 {
+        match self.total {
+            0 => 1.0,
+            total => self.loaded as f32 / total as f32,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct ProgressFill;
+
+fn start_loading(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    levels: Res<Levels>,
+    current_level: Res<CurrentLevel>,
+    mut progress: ResMut<LoadingProgress>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(level) = levels.get(&current_level.0) else {
+        error!(
+            level = current_level.0,
+            "no level with this name, returning to menu"
+        );
+        commands.remove_resource::<GameAssets>();
+        next_state.set(AppState::MainMenu);
+        return;
+    };
+
+    let assets = GameAssets::load(&asset_server, level);
+    debug!(level = level.name, ?assets, "loading assets");
+
+    *progress = LoadingProgress {
+        loaded: 0,
+        total: assets.ids().len(),
+    };
+    commands.insert_resource(assets);
+}
+
+fn spawn_loading_screen(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Loading Screen"),
+        StateScoped(AppState::Loading),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(16.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.05, 0.05, 0.07)),
+        children![
+            (Text::new("Loading"), TextFont::from_font_size(32.0)),
+            (
+                Node {
+                    width: Val::Px(BAR_WIDTH),
+                    height: Val::Px(12.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.24)),
+                children![(
+                    ProgressFill,
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.8, 0.8, 0.9)),
+                )],
+            ),
+        ],
+    ));
+}
+
+/// The level can't be played without its map, anything else that fails is logged and skipped.
+fn track_loading(
+    asset_server: Res<AssetServer>,
+    assets: Option<Res<GameAssets>>,
+    mut progress: ResMut<LoadingProgress>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+
+    if let RecursiveDependencyLoadState::Failed(error) =
+        asset_server.recursive_dependency_load_state(&assets.map)
+    {
+        error!(path = ?assets.map.path(), %error, "failed to load map, returning to menu");
+        next_state.set(AppState::MainMenu);
+        return;
+    }
+
+    let states: Vec<_> = assets
+        .ids()
+        .into_iter()
+        .map(|id| (id, asset_server.recursive_dependency_load_state(id)))
+        .collect();
+    progress.loaded = states
+        .iter()
+        .filter(|(_, state)| state.is_loaded() || state.is_failed())
+        .count();
+    if progress.loaded < progress.total {
+        return;
+    }
+
+    for (id, state) in states {
+        if let RecursiveDependencyLoadState::Failed(error) = state {
+            error!(path = ?asset_server.get_path(id), %error, "failed to load asset, skipping it");
+        }
+    }
+
+    info!("assets loaded");
+    next_state.set(AppState::Gameplay);
+}
+
+fn update_progress_bar(
+    progress: Res<LoadingProgress>,
+    mut fill: Single<&mut Node, With<ProgressFill>>,
+) {
+    fill.width = Val::Percent(progress.fraction() * 100.0);
+}
@@ -0,0 +1,57 @@
+use crate::AppState;
+use crate::level::{FIRST_LEVEL, LoadLevel};
+use crate::widgets::{menu_button, menu_screen};
+use bevy::prelude::*;
+use bevy::window::{CursorGrabMode, PrimaryWindow};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+        .add_systems(
+            Update,
+            handle_main_menu_buttons.run_if(in_state(AppState::MainMenu)),
+        );
+}
+
+#[derive(Component, Debug)]
+enum MainMenuButton {
+    Play,
+    Quit,
+}
+
+fn spawn_main_menu(mut commands: Commands, mut window: Single<&mut Window, With<PrimaryWindow>>) {
+    window.cursor_options.grab_mode = CursorGrabMode::None;
+    window.cursor_options.visible = true;
+
+    commands
+        .spawn((
+            menu_screen(
+                "Main Menu",
+                "Monarchs descent",
+                Color::srgb(0.05, 0.05, 0.07),
+            ),
+            StateScoped(AppState::MainMenu),
+        ))
+        .with_children(|menu| {
+            menu.spawn(menu_button("Play", MainMenuButton::Play));
+            menu.spawn(menu_button("Quit", MainMenuButton::Quit));
+        });
+}
+
+fn handle_main_menu_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &MainMenuButton), Changed<Interaction>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MainMenuButton::Play => commands.queue(LoadLevel(FIRST_LEVEL.to_string())),
+            MainMenuButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+        }
+    }
+}
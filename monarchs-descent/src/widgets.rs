@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+const BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.24);
+const HOVERED_BUTTON_COLOR: Color = Color::srgb(0.3, 0.3, 0.38);
+const PRESSED_BUTTON_COLOR: Color = Color::srgb(0.4, 0.4, 0.5);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, highlight_menu_buttons);
+}
+
+/// Button in a full screen menu, coloured by its [`Interaction`].
+#[derive(Component, Debug)]
+pub(crate) struct MenuButton;
+
+/// Full screen column with `title` at the top, menu buttons are spawned as its children.
+pub(crate) fn menu_screen(name: &'static str, title: &str, background: Color) -> impl Bundle {
+    (
+        Name::new(name),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(background),
+        children![(
+            Text::new(title),
+            TextFont::from_font_size(40.0),
+            Node {
+                margin: UiRect::bottom(Val::Px(24.0)),
+                ..default()
+            },
+        )],
+    )
+}
+
+/// Menu button showing `label`, `action` tells the menu what to do when it is pressed.
+pub(crate) fn menu_button(label: &str, action: impl Component) -> impl Bundle {
+    (
+        Button,
+        MenuButton,
+        action,
+        Node {
+            width: Val::Px(240.0),
+            padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_COLOR),
+        children![(Text::new(label), TextFont::from_font_size(20.0))],
+    )
+}
+
+fn highlight_menu_buttons(
+    mut buttons: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<MenuButton>),
+    >,
+) {
+    for (interaction, mut color) in buttons.iter_mut() {
+        color.0 = match interaction {
+            Interaction::Pressed => PRESSED_BUTTON_COLOR,
+            Interaction::Hovered => HOVERED_BUTTON_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        };
+    }
+}